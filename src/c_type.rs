@@ -0,0 +1,717 @@
+use crate::r#type::{Type, TypeList};
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use std::{collections::HashSet, fmt};
+
+/// The width of a C integer type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Width {
+    Char,
+    Short,
+    Int,
+    Long,
+    LongLong,
+    Size,
+    /// Fixed width integers such as `int8_t`.
+    Bits(u8),
+}
+
+/// The type a C declaration is built from, before any pointers or arrays.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Base {
+    Void,
+    Bool,
+    /// A plain `char`, strings are built from it.
+    Char,
+    Integer {
+        unsigned: bool,
+        width: Width,
+    },
+    Float,
+    Double,
+    /// A struct, enum or typedef.
+    Named(String),
+    FunctionPointer(Box<FunctionPointer>),
+    VaList,
+    /// The `...` of a variadic function.
+    Variadic,
+    /// A struct or union declared in place, kept as is.
+    Inline(String),
+}
+
+/// The signature of a C function pointer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionPointer {
+    pub ret: CType,
+    pub args: Vec<(CType, Option<String>)>,
+}
+
+/// A parsed C type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CType {
+    /// Whether the base type is `const`.
+    is_const: bool,
+    base: Base,
+    /// Every level of pointer indirection, with whether that pointer is `const`.
+    pointers: Vec<bool>,
+    /// Whether this is a C++ reference.
+    is_reference: bool,
+    /// Array dimensions, `None` for an unsized `[]`.
+    dimensions: Vec<Option<String>>,
+}
+
+impl CType {
+    /// Parse a C type as found in the cimgui output.
+    pub fn parse(r#type: &str) -> Result<Self> {
+        let r#type = r#type.trim();
+
+        // Structs & unions declared in place are only used for struct fields, their body is kept
+        // verbatim including the semicolon of the last member
+        if r#type.contains('{') {
+            return Ok(Self::from_base(Base::Inline(r#type.to_string())));
+        }
+
+        let r#type = r#type.trim_end_matches(';').trim();
+
+        let tokens = tokenize(r#type);
+        let (c_type, name) = parse_declaration(&tokens)
+            .map_err(|err| anyhow!("Could not parse C type \"{}\": {}", r#type, err))?;
+        match name {
+            Some(name) => Err(anyhow!(
+                "Unexpected name \"{}\" in C type \"{}\"",
+                name,
+                r#type
+            )),
+            None => Ok(c_type),
+        }
+    }
+
+    /// A type without qualifiers, pointers or arrays.
+    pub fn from_base(base: Base) -> Self {
+        Self {
+            is_const: false,
+            base,
+            pointers: vec![],
+            is_reference: false,
+            dimensions: vec![],
+        }
+    }
+
+    /// Add array dimensions, such as the `[512]` of a struct field.
+    pub fn with_dimensions(mut self, dimensions: Vec<Option<String>>) -> Self {
+        self.dimensions.extend(dimensions);
+        self
+    }
+
+    /// The name of the struct, enum or typedef this type is built from.
+    pub fn name(&self) -> Option<&str> {
+        match &self.base {
+            Base::Named(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Whether the base type is `const`, such as the `char` of `const char*`.
+    pub fn is_const(&self) -> bool {
+        self.is_const
+    }
+
+    /// Whether this is the `void` type without pointers.
+    pub fn is_void(&self) -> bool {
+        self.base == Base::Void && self.pointers.is_empty() && self.dimensions.is_empty()
+    }
+
+    /// Whether this is a `bool` without pointers.
+    pub fn is_bool(&self) -> bool {
+        self.base == Base::Bool && !self.is_indirect()
+    }
+
+    /// Whether this is an integer without pointers, such as the number of elements of an array.
+    pub fn is_integer(&self) -> bool {
+        matches!(self.base, Base::Integer { .. }) && !self.is_indirect()
+    }
+
+    /// Whether this is a `size_t`, as used for the size of buffers.
+    pub fn is_size(&self) -> bool {
+        matches!(
+            self.base,
+            Base::Integer {
+                width: Width::Size,
+                ..
+            }
+        ) && !self.is_indirect()
+    }
+
+    /// Whether this is a C string.
+    pub fn is_string(&self) -> bool {
+        self.base == Base::Char && self.pointers.len() == 1 && self.dimensions.is_empty()
+    }
+
+    /// Whether this is a pointer or array of any kind.
+    pub fn is_indirect(&self) -> bool {
+        !self.pointers.is_empty() || !self.dimensions.is_empty() || self.is_reference
+    }
+
+    /// Whether this is an array of any kind.
+    pub fn is_array(&self) -> bool {
+        !self.dimensions.is_empty()
+    }
+
+    /// The number of elements of a fixed-size, one-dimensional array.
+    pub fn array_len(&self) -> Option<usize> {
+        match self.dimensions.as_slice() {
+            [Some(len)] if self.pointers.is_empty() => len.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// The type this pointer or array points to.
+    pub fn pointee(&self) -> Option<Self> {
+        let mut pointee = self.clone();
+        if pointee.dimensions.pop().is_none() && pointee.pointers.pop().is_none() {
+            return None;
+        }
+        pointee.is_reference = false;
+
+        Some(pointee)
+    }
+
+    /// Whether this is the `...` of a variadic function.
+    pub fn is_ellipsis(&self) -> bool {
+        self.base == Base::Variadic
+    }
+
+    /// Whether this is a function pointer, such as the `ImGuiInputTextCallback` typedef once
+    /// resolved.
+    pub fn is_function_pointer(&self) -> bool {
+        matches!(self.base, Base::FunctionPointer(_))
+            && self.pointers.is_empty()
+            && self.dimensions.is_empty()
+    }
+
+    /// Whether this is a `va_list`, which can't be constructed from LuaJIT.
+    pub fn is_va_list(&self) -> bool {
+        self.base == Base::VaList
+    }
+
+    /// Whether this is the `...` or `va_list` of a variadic function, which has no single Lua value.
+    /// The values of `...` are formatted in Lua, functions taking a `va_list` are skipped.
+    pub fn is_variadic(&self) -> bool {
+        matches!(self.base, Base::Variadic | Base::VaList)
+    }
+
+    /// Follow the typedefs until a builtin type, struct, enum, function pointer or opaque type is
+    /// reached.
+    pub fn resolve(&self, types: &[Type]) -> Self {
+        let mut resolved = self.clone();
+
+        let mut visited = HashSet::new();
+        while let Base::Named(name) = &resolved.base {
+            if !visited.insert(name.clone()) {
+                break;
+            }
+
+            let typedef = match types.find(name) {
+                Ok(Type::Typedef(typedef)) => typedef,
+                _ => break,
+            };
+
+            // Flag typedefs resolve to their enum
+            if let Some(Type::Enum(r#enum)) = typedef.target(types) {
+                resolved.base = Base::Named(r#enum.name().to_string());
+                break;
+            }
+
+            let target = typedef.c_type();
+            if target.name() == Some(name) {
+                // Opaque struct
+                break;
+            }
+
+            // The pointers of the typedef are closer to the base type
+            let mut pointers = target.pointers.clone();
+            let is_const = match pointers.last_mut() {
+                Some(is_const_pointer) => {
+                    *is_const_pointer |= resolved.is_const;
+                    target.is_const
+                }
+                None => target.is_const || resolved.is_const,
+            };
+            pointers.extend(resolved.pointers.iter());
+
+            resolved = Self {
+                is_const,
+                base: target.base.clone(),
+                pointers,
+                is_reference: resolved.is_reference,
+                dimensions: target
+                    .dimensions
+                    .iter()
+                    .chain(resolved.dimensions.iter())
+                    .cloned()
+                    .collect(),
+            };
+        }
+
+        resolved
+    }
+
+    /// The Lua type of a value of this type.
+    pub fn lua_type(&self, types: &[Type]) -> Result<String> {
+        let resolved = self.resolve(types);
+
+        if resolved.is_variadic() {
+            return Err(anyhow!(
+                "Variadic C type \"{}\" has no Lua equivalent",
+                self
+            ));
+        }
+        if resolved.is_string() {
+            return Ok("string".to_string());
+        }
+        if resolved.is_indirect() {
+            return Ok("cdata".to_string());
+        }
+
+        match &resolved.base {
+            Base::Bool => Ok("boolean".to_string()),
+            Base::Char | Base::Integer { .. } | Base::Float | Base::Double => {
+                Ok("number".to_string())
+            }
+            Base::Named(name) => types.find(name)?.lua_primitive_type(),
+            Base::FunctionPointer(_) => Ok("cdata".to_string()),
+            _ => Err(anyhow!("C type \"{}\" has no Lua equivalent", self)),
+        }
+    }
+
+    /// A C declaration of a variable with this type.
+    pub fn declaration(&self, name: &str) -> String {
+        match &self.base {
+            Base::FunctionPointer(func) => func.declaration(&format!("*{}", name)),
+            Base::Inline(_) => format!("{} {}", self, name),
+            Base::Variadic => "...".to_string(),
+            _ => format!(
+                "{} {}{}",
+                self.qualified_pointers(),
+                name,
+                self.dimensions_string()
+            ),
+        }
+    }
+
+    /// The base type with qualifiers & pointers, without the array dimensions.
+    fn qualified_pointers(&self) -> String {
+        format!(
+            "{}{}{}{}",
+            if self.is_const { "const " } else { "" },
+            self.base,
+            self.pointers
+                .iter()
+                .map(|is_const| if *is_const { "* const" } else { "*" })
+                .join(""),
+            if self.is_reference { "&" } else { "" }
+        )
+    }
+
+    fn dimensions_string(&self) -> String {
+        self.dimensions
+            .iter()
+            .map(|dimension| format!("[{}]", dimension.as_deref().unwrap_or("")))
+            .join("")
+    }
+}
+
+impl fmt::Display for CType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.base {
+            Base::FunctionPointer(func) => write!(f, "{}", func.declaration("*")),
+            Base::Inline(inline) => write!(f, "{}", inline),
+            _ => write!(
+                f,
+                "{}{}",
+                self.qualified_pointers(),
+                self.dimensions_string()
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = |unsigned: &bool| if *unsigned { "unsigned " } else { "" };
+
+        match self {
+            Base::Void => write!(f, "void"),
+            Base::Bool => write!(f, "bool"),
+            Base::Char => write!(f, "char"),
+            Base::Integer {
+                unsigned,
+                width: Width::Char,
+            } => write!(f, "{}char", if *unsigned { "unsigned " } else { "signed " }),
+            Base::Integer {
+                unsigned,
+                width: Width::Short,
+            } => write!(f, "{}short", sign(unsigned)),
+            Base::Integer {
+                unsigned,
+                width: Width::Int,
+            } => write!(f, "{}int", sign(unsigned)),
+            Base::Integer {
+                unsigned,
+                width: Width::Long,
+            } => write!(f, "{}long", sign(unsigned)),
+            Base::Integer {
+                unsigned,
+                width: Width::LongLong,
+            } => write!(f, "{}long long", sign(unsigned)),
+            Base::Integer {
+                width: Width::Size, ..
+            } => write!(f, "size_t"),
+            Base::Integer {
+                unsigned,
+                width: Width::Bits(bits),
+            } => write!(f, "{}int{}_t", if *unsigned { "u" } else { "" }, bits),
+            Base::Float => write!(f, "float"),
+            Base::Double => write!(f, "double"),
+            Base::Named(name) => write!(f, "{}", name),
+            Base::FunctionPointer(func) => write!(f, "{}", func.declaration("*")),
+            Base::VaList => write!(f, "va_list"),
+            Base::Variadic => write!(f, "..."),
+            Base::Inline(inline) => write!(f, "{}", inline),
+        }
+    }
+}
+
+impl FunctionPointer {
+    /// The C declaration with the name, such as `void(*name)(int)`.
+    fn declaration(&self, name: &str) -> String {
+        format!(
+            "{}({})({})",
+            self.ret,
+            name,
+            self.args
+                .iter()
+                .map(|(c_type, name)| match name {
+                    Some(name) => c_type.declaration(name),
+                    None => c_type.to_string(),
+                })
+                .join(",")
+        )
+    }
+}
+
+/// Split a C declaration into tokens.
+fn tokenize(declaration: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut chars = declaration.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '.' => {
+                // Varargs
+                while chars.peek() == Some(&'.') {
+                    chars.next();
+                }
+                tokens.push("...".to_string());
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut token = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' || next == ':' {
+                        token.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(token);
+            }
+            c => tokens.push(c.to_string()),
+        }
+    }
+
+    tokens
+}
+
+/// Split the tokens of a list at the commas that aren't nested in parentheses, such as the ones
+/// between the arguments of a function pointer argument.
+fn split_top_level(tokens: &[String]) -> Vec<&[String]> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        match token.as_str() {
+            "(" => depth += 1,
+            ")" => depth -= 1,
+            "," if depth == 0 => {
+                parts.push(&tokens[start..index]);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+    parts.push(&tokens[start..]);
+
+    parts
+}
+
+/// Parse tokens of a declaration into the type and the optional variable name.
+fn parse_declaration(tokens: &[String]) -> Result<(CType, Option<String>)> {
+    // Function pointers have a `(*` group
+    if let Some(start) = tokens
+        .windows(2)
+        .position(|window| window[0] == "(" && window[1] == "*")
+    {
+        let (ret, _) = parse_declaration(&tokens[..start])?;
+
+        let close = tokens[start..]
+            .iter()
+            .position(|token| token == ")")
+            .map(|index| start + index)
+            .ok_or_else(|| anyhow!("Unclosed function pointer"))?;
+        let name = tokens[start + 2..close].first().cloned();
+
+        // Everything between the argument parentheses
+        let args = &tokens[close + 1..];
+        if args.first().map(|token| token.as_str()) != Some("(")
+            || args.last().map(|token| token.as_str()) != Some(")")
+        {
+            return Err(anyhow!("Missing function pointer arguments"));
+        }
+        let args = split_top_level(&args[1..args.len() - 1])
+            .into_iter()
+            .filter(|arg| !arg.is_empty())
+            .map(parse_declaration)
+            .collect::<Result<Vec<_>>>()?;
+
+        return Ok((
+            CType::from_base(Base::FunctionPointer(Box::new(FunctionPointer {
+                ret,
+                args,
+            }))),
+            name,
+        ));
+    }
+
+    let mut is_const = false;
+    let mut unsigned = None;
+    let mut words: Vec<&str> = vec![];
+    let mut index = 0;
+
+    // The specifiers
+    while let Some(token) = tokens.get(index) {
+        match token.as_str() {
+            "const" => is_const = true,
+            "unsigned" => unsigned = Some(true),
+            "signed" => unsigned = Some(false),
+            "struct" | "enum" | "union" => (),
+            "*" | "&" | "[" => break,
+            word => {
+                // A second non-builtin word is the variable name
+                let is_builtin =
+                    is_builtin_word(word) && words.iter().all(|word| is_builtin_word(word));
+                if !words.is_empty() && !is_builtin {
+                    break;
+                }
+                words.push(word);
+            }
+        }
+        index += 1;
+    }
+
+    let base = match (words.as_slice(), unsigned) {
+        ([], Some(unsigned)) | (["int"], Some(unsigned)) => Base::Integer {
+            unsigned,
+            width: Width::Int,
+        },
+        (["void"], _) => Base::Void,
+        (["bool"], _) => Base::Bool,
+        (["char"], None) => Base::Char,
+        (["char"], Some(unsigned)) => Base::Integer {
+            unsigned,
+            width: Width::Char,
+        },
+        (["short"], unsigned) | (["short", "int"], unsigned) => Base::Integer {
+            unsigned: unsigned.unwrap_or(false),
+            width: Width::Short,
+        },
+        (["int"], None) => Base::Integer {
+            unsigned: false,
+            width: Width::Int,
+        },
+        (["long"], unsigned) | (["long", "int"], unsigned) => Base::Integer {
+            unsigned: unsigned.unwrap_or(false),
+            width: Width::Long,
+        },
+        (["long", "long"], unsigned) | (["long", "long", "int"], unsigned) => Base::Integer {
+            unsigned: unsigned.unwrap_or(false),
+            width: Width::LongLong,
+        },
+        (["size_t"], _) => Base::Integer {
+            unsigned: true,
+            width: Width::Size,
+        },
+        (["float"], _) => Base::Float,
+        (["double"], _) => Base::Double,
+        (["va_list"], _) => Base::VaList,
+        (["..."], _) => Base::Variadic,
+        ([word], None) => match fixed_width_integer(word) {
+            Some(base) => base,
+            None => Base::Named(word.to_string()),
+        },
+        (words, _) => return Err(anyhow!("Unrecognized type \"{}\"", words.join(" "))),
+    };
+
+    let mut c_type = CType {
+        is_const,
+        ..CType::from_base(base)
+    };
+    let mut name = None;
+
+    // The declarator
+    while let Some(token) = tokens.get(index) {
+        match token.as_str() {
+            "*" => c_type.pointers.push(false),
+            "const" => match c_type.pointers.last_mut() {
+                Some(is_const) => *is_const = true,
+                None => c_type.is_const = true,
+            },
+            "&" => c_type.is_reference = true,
+            "[" => {
+                let close = tokens[index..]
+                    .iter()
+                    .position(|token| token == "]")
+                    .map(|close| index + close)
+                    .ok_or_else(|| anyhow!("Unclosed array dimension"))?;
+                let dimension = tokens[index + 1..close].join("");
+                c_type.dimensions.push(if dimension.is_empty() {
+                    None
+                } else {
+                    Some(dimension)
+                });
+                index = close;
+            }
+            word if name.is_none() => name = Some(word.to_string()),
+            other => return Err(anyhow!("Unexpected token \"{}\"", other)),
+        }
+        index += 1;
+    }
+
+    Ok((c_type, name))
+}
+
+/// Words that can be combined to form a single builtin type.
+fn is_builtin_word(word: &str) -> bool {
+    matches!(word, "char" | "short" | "int" | "long")
+}
+
+/// The `<stdint.h>` types.
+fn fixed_width_integer(word: &str) -> Option<Base> {
+    let (unsigned, bits) = match word.strip_prefix('u') {
+        Some(word) => (true, word),
+        None => (false, word),
+    };
+    let bits = bits.strip_prefix("int")?.strip_suffix("_t")?.parse().ok()?;
+
+    Some(Base::Integer {
+        unsigned,
+        width: Width::Bits(bits),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Base, CType, Width};
+
+    #[test]
+    fn parse() -> anyhow::Result<()> {
+        let string = CType::parse("const char*")?;
+        assert!(string.is_string());
+        assert!(string.is_const);
+
+        let items = CType::parse("const char* const[]")?;
+        assert_eq!(items.pointers.len(), 1);
+        assert_eq!(items.dimensions, vec![None]);
+        assert_eq!(items.pointee(), Some(CType::parse("const char* const")?));
+        assert_eq!(
+            CType::parse("bool*")?.pointee(),
+            Some(CType::parse("bool")?)
+        );
+        assert_eq!(CType::parse("int")?.pointee(), None);
+
+        assert_eq!(
+            CType::parse("float[3]")?.dimensions,
+            vec![Some("3".to_string())]
+        );
+        assert_eq!(
+            CType::parse("unsigned char**")?.base,
+            Base::Integer {
+                unsigned: true,
+                width: Width::Char
+            }
+        );
+        assert_eq!(
+            CType::parse("unsigned long long")?.base,
+            Base::Integer {
+                unsigned: true,
+                width: Width::LongLong
+            }
+        );
+        assert_eq!(
+            CType::parse("uint32_t")?.base,
+            Base::Integer {
+                unsigned: true,
+                width: Width::Bits(32)
+            }
+        );
+        assert!(CType::parse("ImVec2&")?.is_reference);
+        assert!(CType::parse("va_list")?.is_variadic());
+
+        let getter = CType::parse("bool(*)(void* data,int idx,const char** out_text)")?;
+        match getter.base {
+            Base::FunctionPointer(func) => {
+                assert_eq!(func.ret.base, Base::Bool);
+                assert_eq!(func.args.len(), 3);
+                assert_eq!(func.args[2].1.as_deref(), Some("out_text"));
+                assert_eq!(func.args[2].0.pointers.len(), 2);
+            }
+            _ => panic!("Not a function pointer"),
+        }
+
+        // The commas of nested function pointers don't split the outer arguments
+        let nested = CType::parse("void(*)(void(*callback)(int a,float b),void* user_data)")?;
+        match nested.base {
+            Base::FunctionPointer(func) => {
+                assert_eq!(func.args.len(), 2);
+                assert_eq!(func.args[0].1.as_deref(), Some("callback"));
+                assert!(func.args[0].0.is_function_pointer());
+                assert_eq!(func.args[1].1.as_deref(), Some("user_data"));
+            }
+            _ => panic!("Not a function pointer"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn display() -> anyhow::Result<()> {
+        for c_type in &[
+            "const char*",
+            "const char* const[]",
+            "unsigned int",
+            "float[3]",
+            "ImVec2*",
+            "bool(*)(void* data,int idx,const char** out_text)",
+        ] {
+            assert_eq!(CType::parse(c_type)?.to_string(), *c_type);
+        }
+
+        assert_eq!(
+            CType::parse("const char* const[]")?.declaration("items"),
+            "const char* const items[]"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+use std::convert::TryFrom;
+
+/// Read the `//` comments documenting the declaration on the 1-based line of a header, both the
+/// block directly above it and the one at the end of the line. Section titles such as
+/// `// Widgets: Main` aren't part of the documentation of the first declaration below them.
+pub fn extract(lines: &[String], line_number: i64) -> Option<String> {
+    let index = usize::try_from(line_number).ok()?.checked_sub(1)?;
+    let declaration = lines.get(index)?;
+
+    let preceding = block_above(lines, index);
+    let preceding = if is_title(lines, index, preceding.len()) {
+        Vec::new()
+    } else {
+        preceding
+    };
+
+    let comment = preceding
+        .into_iter()
+        .rev()
+        .chain(trailing(declaration))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if comment.trim().is_empty() {
+        None
+    } else {
+        Some(comment.trim().to_string())
+    }
+}
+
+/// The title of the section of the header the declaration on the 1-based line belongs to, such as
+/// `Widgets: Main`.
+pub fn section(lines: &[String], line_number: i64) -> Option<String> {
+    let mut index = usize::try_from(line_number).ok()?.checked_sub(1)?;
+    lines.get(index)?;
+
+    // Walk up past the other declarations of the section and their documentation
+    loop {
+        let block = block_above(lines, index);
+        if is_title(lines, index, block.len()) {
+            return block
+                .last()
+                .map(|title| title.trim_start_matches("[SECTION]").trim().to_string());
+        }
+
+        index = index.checked_sub(block.len() + 1)?;
+    }
+}
+
+/// Prefix every line of the comment, such as `---` for LuaLS or `--` for Teal.
+pub fn prefixed(comment: Option<&str>, prefix: &str) -> String {
+    comment.map_or(String::new(), |comment| {
+        comment
+            .lines()
+            .map(|line| match line {
+                "" => format!("{}\n", prefix),
+                line => format!("{} {}\n", prefix, line),
+            })
+            .collect()
+    })
+}
+
+/// The comment at the end of a line of code.
+fn trailing(line: &str) -> Option<&str> {
+    comment_start(line).map(|start| line[start..].trim_start_matches('/').trim())
+}
+
+/// The index of the `//` starting the comment of a line, `//` inside string literals doesn't start
+/// one.
+fn comment_start(line: &str) -> Option<usize> {
+    let mut in_string = false;
+    let mut previous = None;
+    for (index, char) in line.char_indices() {
+        match char {
+            '"' if previous != Some('\\') => in_string = !in_string,
+            '/' if !in_string && previous == Some('/') => return Some(index - 1),
+            _ => (),
+        }
+        previous = Some(char);
+    }
+
+    None
+}
+
+/// Whether the declaration and the other line are both one-line declarations at the same
+/// indentation, such as the functions of a section.
+fn is_grouped(declaration: &str, other: Option<&String>) -> bool {
+    let is_one_line = |line: &str| {
+        line[..comment_start(line).unwrap_or(line.len())]
+            .trim_end()
+            .ends_with(';')
+    };
+    let indentation = |line: &str| line.len() - line.trim_start().len();
+
+    other.is_some_and(|other| {
+        is_one_line(declaration)
+            && is_one_line(other)
+            && indentation(declaration) == indentation(other)
+    })
+}
+
+/// The `//` comment lines directly above the line at the index, closest first. The walk stops at
+/// the first line that isn't a comment, so a title followed by a blank line or a separator is
+/// never reached.
+fn block_above(lines: &[String], index: usize) -> Vec<&str> {
+    lines[..index]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with("//") && !is_separator(line))
+        .map(|line| line.trim_start_matches('/').trim())
+        .collect()
+}
+
+/// Whether the comment block of `len` lines above the line at the index titles a section. That's
+/// the case when it's followed by a blank line or a separator, when it's shared by a group of
+/// declarations and when it interrupts one to start a new section.
+fn is_title(lines: &[String], index: usize, len: usize) -> bool {
+    let line = &lines[index];
+    let above = index
+        .checked_sub(len + 1)
+        .and_then(|above| lines.get(above));
+
+    len > 0
+        && (line.trim().is_empty()
+            || line.trim().starts_with("//")
+            || is_grouped(line, lines.get(index + 1))
+            || is_grouped(line, above))
+}
+
+/// Lines such as `//-----` dividing the sections of a header.
+fn is_separator(line: &str) -> bool {
+    line.trim_start_matches('/')
+        .chars()
+        .all(|char| char == '-' || char == '=' || char == '/')
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn extract() -> anyhow::Result<()> {
+        let lines = indoc::indoc!(
+            r#"
+            //-----------------------------------------------------------------------------
+            // Windows
+            // - Begin() = push window to the stack and start appending to it.
+                IMGUI_API bool Begin(const char* name, bool* p_open = NULL);
+                IMGUI_API void End();
+                IMGUI_API bool SliderFloat(const char* format = "%.3f // not a comment"); // adjust format
+
+            // Helper: Parse and apply text filters.
+            struct ImGuiTextFilter
+            {
+            // Widgets: Text
+
+                IMGUI_API void Text(const char* fmt, ...); // formatted text
+            // Widgets: Main
+            //-----------------------------------------------------------------------------
+                IMGUI_API bool Button(const char* label);
+
+            // Shows the demo window
+                IMGUI_API void ShowDemoWindow(bool* p_open = NULL);
+            // Widgets: Color Editor/Picker
+                IMGUI_API bool ColorEdit3(const char* label, float col[3]);
+            "#
+        )
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+        // Section titles are shared by the declarations below them
+        assert_eq!(super::extract(&lines, 4), None);
+        assert_eq!(super::extract(&lines, 5), None);
+        assert_eq!(super::extract(&lines, 6), Some("adjust format".to_string()));
+        assert_eq!(
+            super::extract(&lines, 9),
+            Some("Helper: Parse and apply text filters.".to_string())
+        );
+        // Titles followed by a blank line or a separator
+        assert_eq!(
+            super::extract(&lines, 13),
+            Some("formatted text".to_string())
+        );
+        assert_eq!(super::extract(&lines, 16), None);
+        assert_eq!(
+            super::extract(&lines, 19),
+            Some("Shows the demo window".to_string())
+        );
+        // A title interrupting a group of declarations
+        assert_eq!(super::extract(&lines, 21), None);
+        assert_eq!(super::extract(&lines, 100), None);
+
+        assert_eq!(super::section(&lines, 5), Some("Windows".to_string()));
+        assert_eq!(
+            super::section(&lines, 13),
+            Some("Widgets: Text".to_string())
+        );
+        assert_eq!(
+            super::section(&lines, 16),
+            Some("Widgets: Main".to_string())
+        );
+        assert_eq!(
+            super::section(&lines, 19),
+            Some("Widgets: Main".to_string())
+        );
+        assert_eq!(
+            super::section(&lines, 21),
+            Some("Widgets: Color Editor/Picker".to_string())
+        );
+        assert_eq!(super::section(&lines, 100), None);
+
+        assert_eq!(
+            super::prefixed(Some("Windows\n\n- Begin()"), "---"),
+            "--- Windows\n---\n--- - Begin()\n"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+use crate::r#type::Type;
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use std::{iter::Peekable, str::Chars};
+
+/// Translate a C++ default argument expression, such as `ImVec2(0,0)` or `-1.0f`, into Lua.
+pub fn lua_expression(expression: &str, types: &[Type]) -> Result<String> {
+    let tokens = tokenize(expression)
+        .map_err(|err| anyhow!("Could not translate \"{}\": {}", expression, err))?;
+    let mut tokens = tokens.iter().peekable();
+
+    let lua = parse_expression(&mut tokens, types)
+        .map_err(|err| anyhow!("Could not translate \"{}\": {}", expression, err))?;
+    match tokens.next() {
+        Some(token) => Err(anyhow!(
+            "Could not translate \"{}\": unexpected {:?}",
+            expression,
+            token
+        )),
+        None => Ok(lua),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Number(String),
+    String(String),
+    Identifier(String),
+    Symbol(char),
+}
+
+type Tokens<'a> = Peekable<std::slice::Iter<'a, Token>>;
+
+/// Split the expression into numbers, strings, identifiers & symbols.
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+
+    let mut chars = expression.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '"' => tokens.push(Token::String(string_literal(&mut chars)?)),
+            c if c.is_ascii_digit() || c == '.' => {
+                tokens.push(Token::Number(number_literal(&mut chars)?))
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut identifier = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !c.is_alphanumeric() && c != '_' && c != ':' {
+                        break;
+                    }
+                    identifier.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Identifier(identifier));
+            }
+            '(' | ')' | ',' | '-' | '+' | '|' => {
+                tokens.push(Token::Symbol(c));
+                chars.next();
+            }
+            c => return Err(anyhow!("unsupported character '{}'", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A C string literal including the quotes, the escape sequences are the same in Lua.
+fn string_literal(chars: &mut Peekable<Chars>) -> Result<String> {
+    let mut literal = String::new();
+    literal.extend(chars.next());
+
+    while let Some(c) = chars.next() {
+        literal.push(c);
+        match c {
+            '\\' => literal.extend(chars.next()),
+            '"' => return Ok(literal),
+            _ => (),
+        }
+    }
+
+    Err(anyhow!("unterminated string {}", literal))
+}
+
+/// A C number literal without the type suffixes, such as the `f` of `1.0f`.
+fn number_literal(chars: &mut Peekable<Chars>) -> Result<String> {
+    let mut literal = String::new();
+    while let Some(&c) = chars.peek() {
+        let is_hex = literal.starts_with("0x") || literal.starts_with("0X");
+        // The sign of an exponent, such as in `1e-5f`
+        let is_exponent_sign =
+            (c == '-' || c == '+') && !is_hex && literal.ends_with(&['e', 'E'][..]);
+        if !c.is_ascii_alphanumeric() && c != '.' && !is_exponent_sign {
+            break;
+        }
+        literal.push(c);
+        chars.next();
+    }
+
+    let is_hex = literal.starts_with("0x") || literal.starts_with("0X");
+    let number = if is_hex {
+        literal.trim_end_matches(&['u', 'U', 'l', 'L'][..])
+    } else {
+        literal.trim_end_matches(&['f', 'F', 'u', 'U', 'l', 'L'][..])
+    };
+
+    // Lua doesn't accept a trailing dot as in `1.`
+    let number = number.trim_end_matches('.');
+    if number.is_empty() || (!is_hex && number.parse::<f64>().is_err()) {
+        return Err(anyhow!("invalid number {}", literal));
+    }
+
+    Ok(number.to_string())
+}
+
+/// Values separated by `|`.
+fn parse_expression(tokens: &mut Tokens, types: &[Type]) -> Result<String> {
+    let mut values = vec![parse_unary(tokens, types)?];
+    while tokens.peek() == Some(&&Token::Symbol('|')) {
+        tokens.next();
+        values.push(parse_unary(tokens, types)?);
+    }
+
+    if values.len() == 1 {
+        Ok(values.remove(0))
+    } else {
+        Ok(format!("bit.bor({})", values.join(", ")))
+    }
+}
+
+/// A value with an optional sign.
+fn parse_unary(tokens: &mut Tokens, types: &[Type]) -> Result<String> {
+    match tokens.peek() {
+        Some(Token::Symbol('-')) => {
+            tokens.next();
+            let value = parse_unary(tokens, types)?;
+            // `--` starts a comment in Lua
+            if value.starts_with('-') {
+                Ok(format!("-({})", value))
+            } else {
+                Ok(format!("-{}", value))
+            }
+        }
+        Some(Token::Symbol('+')) => {
+            tokens.next();
+            parse_unary(tokens, types)
+        }
+        _ => parse_value(tokens, types),
+    }
+}
+
+/// A literal, constant, parenthesized expression or constructor call.
+fn parse_value(tokens: &mut Tokens, types: &[Type]) -> Result<String> {
+    match tokens.next() {
+        Some(Token::Number(number)) => Ok(number.clone()),
+        Some(Token::String(string)) => Ok(string.clone()),
+        Some(Token::Symbol('(')) => {
+            let value = parse_expression(tokens, types)?;
+            expect(tokens, ')')?;
+            Ok(format!("({})", value))
+        }
+        Some(Token::Identifier(identifier)) if identifier == "sizeof" => {
+            expect(tokens, '(')?;
+            let mut words = vec![];
+            while let Some(Token::Identifier(word)) = tokens.peek() {
+                words.push(word.as_str());
+                tokens.next();
+            }
+            expect(tokens, ')')?;
+            Ok(format!("ffi.sizeof(\"{}\")", words.join(" ")))
+        }
+        Some(Token::Identifier(identifier)) if tokens.peek() == Some(&&Token::Symbol('(')) => {
+            tokens.next();
+            let mut args = vec![];
+            if tokens.peek() != Some(&&Token::Symbol(')')) {
+                args.push(parse_expression(tokens, types)?);
+                while tokens.peek() == Some(&&Token::Symbol(',')) {
+                    tokens.next();
+                    args.push(parse_expression(tokens, types)?);
+                }
+            }
+            expect(tokens, ')')?;
+            constructor(identifier, &args, types)
+        }
+        Some(Token::Identifier(identifier)) => constant(identifier, types),
+        Some(token) => Err(anyhow!("unexpected {:?}", token)),
+        None => Err(anyhow!("unexpected end")),
+    }
+}
+
+/// Consume the symbol or fail.
+fn expect(tokens: &mut Tokens, symbol: char) -> Result<()> {
+    match tokens.next() {
+        Some(Token::Symbol(c)) if *c == symbol => Ok(()),
+        Some(token) => Err(anyhow!("expected '{}' but got {:?}", symbol, token)),
+        None => Err(anyhow!("expected '{}'", symbol)),
+    }
+}
+
+/// A struct constructor such as `ImVec2(0,0)`.
+fn constructor(name: &str, args: &[String], types: &[Type]) -> Result<String> {
+    match types.iter().find(|r#type| r#type.is_same(name)) {
+        Some(Type::Struct(_)) => Ok(format!(
+            "ffi.new(\"{}\"{})",
+            name,
+            args.iter().map(|arg| format!(", {}", arg)).join("")
+        )),
+        _ => Err(anyhow!("unknown constructor {}", name)),
+    }
+}
+
+/// A builtin or enum constant.
+fn constant(name: &str, types: &[Type]) -> Result<String> {
+    let value = match name {
+        "NULL" | "nullptr" => "nil",
+        "true" => "true",
+        "false" => "false",
+        "FLT_MAX" => "3.402823466e+38",
+        "FLT_MIN" => "1.175494351e-38",
+        "DBL_MAX" => "1.7976931348623158e+308",
+        "INT_MAX" => "2147483647",
+        "INT_MIN" => "-2147483648",
+        "UINT_MAX" => "4294967295",
+        _ => {
+            return types
+                .iter()
+                .find_map(|r#type| match r#type {
+                    Type::Enum(r#enum) => r#enum.value(name),
+                    _ => None,
+                })
+                .map(|value| value.to_string())
+                .ok_or_else(|| anyhow!("unknown constant {}", name))
+        }
+    };
+
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lua_expression;
+
+    #[test]
+    fn translate() -> anyhow::Result<()> {
+        use crate::{
+            r#enum::{Enum, Value},
+            r#struct::Struct,
+            r#type::Type,
+        };
+
+        let types = [
+            Type::Struct(Struct::from_parsed("ImVec2".into(), vec![])),
+            Type::Enum(Enum::from_parsed(
+                "ImGuiWindowFlags_".to_string(),
+                vec![
+                    Value::from_parsed("ImGuiWindowFlags_None".to_string(), 0),
+                    Value::from_parsed("ImGuiWindowFlags_NoTitleBar".to_string(), 1),
+                ],
+            )),
+        ];
+
+        assert_eq!(
+            lua_expression("ImVec2(0,0)", &types)?,
+            "ffi.new(\"ImVec2\", 0, 0)"
+        );
+        assert_eq!(
+            lua_expression("ImVec2(-FLT_MIN,0)", &types)?,
+            "ffi.new(\"ImVec2\", -1.175494351e-38, 0)"
+        );
+        assert_eq!(lua_expression("FLT_MAX", &types)?, "3.402823466e+38");
+        assert_eq!(lua_expression("NULL", &types)?, "nil");
+        assert_eq!(
+            lua_expression("sizeof(float)", &types)?,
+            "ffi.sizeof(\"float\")"
+        );
+        assert_eq!(lua_expression("ImGuiWindowFlags_None", &types)?, "0");
+        assert_eq!(
+            lua_expression("ImGuiWindowFlags_None|ImGuiWindowFlags_NoTitleBar", &types)?,
+            "bit.bor(0, 1)"
+        );
+        assert_eq!(lua_expression("-1.0f", &types)?, "-1.0");
+        assert_eq!(lua_expression("- -1", &types)?, "-(-1)");
+        assert_eq!(lua_expression("1.f", &types)?, "1");
+        assert_eq!(lua_expression("1e-5f", &types)?, "1e-5");
+        assert_eq!(lua_expression("2.5E+3", &types)?, "2.5E+3");
+        assert_eq!(lua_expression("\"%.3f\"", &types)?, "\"%.3f\"");
+        assert_eq!(
+            lua_expression("\"Filter(inc,-exc)\"", &types)?,
+            "\"Filter(inc,-exc)\""
+        );
+
+        assert!(lua_expression("ImColor(1,1,1)", &types).is_err());
+        assert!(lua_expression("SOME_MACRO", &types).is_err());
+        assert!(lua_expression("1 ? 2 : 3", &types).is_err());
+
+        Ok(())
+    }
+}
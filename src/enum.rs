@@ -1,9 +1,14 @@
+use crate::{comment, markdown, name, r#type::Type, render::Render};
+use indoc::indoc;
+use itertools::Itertools;
+
 /// Represents an ImGui structure.
 #[derive(Debug, Default)]
 pub struct Enum {
     name: String,
     values: Vec<Value>,
     location: Option<(String, i64)>,
+    comment: Option<String>,
 }
 
 impl Enum {
@@ -21,27 +26,231 @@ impl Enum {
         self.location = Some((filename.to_string(), line_number));
     }
 
+    /// Add the documentation from the comments in the ImGui header.
+    pub fn add_comment(&mut self, comment: String) {
+        self.comment = Some(comment);
+    }
+
     /// Check if this type is the same as the string.
     pub fn is_same(&self, r#type: &str) -> bool {
         self.name == r#type
     }
+
+    /// The name of the enum.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name in Lua, without the `ImGui` prefix & trailing underscore.
+    pub fn lua_name(&self) -> &str {
+        name::lua_type(&self.name)
+    }
+
+    /// The Markdown reference section with all values.
+    pub fn markdown(&self, imgui_url: &str) -> String {
+        format!(
+            "## `gui.{name}`\n\nWraps `{imgui}`{location}.\n\n{comment}{values}",
+            name = self.lua_name(),
+            imgui = self.name,
+            location = self
+                .location
+                .as_ref()
+                .map_or(String::new(), |location| format!(
+                    " from {}",
+                    markdown::location_link(location, imgui_url)
+                )),
+            comment = self
+                .comment
+                .as_ref()
+                .map_or(String::new(), |comment| format!(
+                    "{}\n",
+                    markdown::paragraph(comment)
+                )),
+            values = markdown::table(
+                &["Name", "Value"],
+                &self
+                    .values
+                    .iter()
+                    .map(|value| vec![
+                        markdown::code(value.lua_name(self)),
+                        markdown::code(&value.calculated_value.to_string())
+                    ])
+                    .collect::<Vec<_>>()
+            )
+        )
+    }
+
+    /// The calculated value of the enum constant with the name.
+    pub fn value(&self, name: &str) -> Option<i64> {
+        self.values
+            .iter()
+            .find(|value| value.name == name)
+            .map(|value| value.calculated_value)
+    }
+}
+
+impl Render for Enum {
+    /// Get the Lua tables of the enum, one from names to values and one for the reverse.
+    fn lua(&self, _types: &[Type]) -> String {
+        format!(
+            indoc! {r#"
+            gui.{name} = {{
+            {values}
+            }}
+            gui.names.{name} = {{
+            {names}
+            }}
+            "#},
+            name = self.lua_name(),
+            values = self
+                .values
+                .iter()
+                .map(|value| format!(
+                    "    {} = {},",
+                    lua_key(value.lua_name(self)),
+                    value.calculated_value
+                ))
+                .join("\n"),
+            // Aliases with the same value are ignored, the first name is used
+            names = self
+                .values
+                .iter()
+                .unique_by(|value| value.calculated_value)
+                .map(|value| format!(
+                    "    [{}] = \"{}\",",
+                    value.calculated_value,
+                    value.lua_name(self)
+                ))
+                .join("\n")
+        )
+    }
+
+    /// Get the LuaLS annotation of the enum table.
+    fn doc(&self, _types: &[Type]) -> String {
+        format!(
+            "{}---@enum {}\n",
+            comment::prefixed(self.comment.as_deref(), "---"),
+            self.lua_name()
+        )
+    }
+
+    /// Get the Teal record of the enum table.
+    fn teal(&self, _types: &[Type]) -> String {
+        format!(
+            "{comment}record {name}\n{values}end\n",
+            comment = comment::prefixed(self.comment.as_deref(), "--"),
+            name = self.lua_name(),
+            values = self
+                .values
+                .iter()
+                .map(|value| value.lua_name(self))
+                // Names that aren't identifiers can only be used with brackets
+                .filter(|name| name::is_lua_identifier(name))
+                .map(|name| format!("    {}: integer\n", name))
+                .collect::<String>()
+        )
+    }
+
+    /// Get the cdef definition of the enum.
+    fn cdef(&self, _types: &[Type]) -> String {
+        format!(
+            "typedef enum {{\n{values}\n}} {name};",
+            name = self.name,
+            values = self
+                .values
+                .iter()
+                .map(|value| format!("    {} = {}", value.name, value.calculated_value))
+                .join(",\n")
+        )
+    }
 }
 
 /// The value variant of an enum.
 #[derive(Debug)]
 pub struct Value {
     name: String,
-    value: String,
     calculated_value: i64,
 }
 
 impl Value {
     /// Add a new enum field from the parsed data.
-    pub fn from_parsed(name: String, value: String, calculated_value: i64) -> Self {
+    pub fn from_parsed(name: String, calculated_value: i64) -> Self {
         Self {
             name,
-            value,
             calculated_value,
         }
     }
+
+    /// The name in Lua, without the prefix of the enum it belongs to.
+    pub fn lua_name(&self, r#enum: &Enum) -> &str {
+        self.name
+            .strip_prefix(r#enum.name())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&self.name)
+    }
+}
+
+/// A Lua table key, names that aren't valid identifiers need to be quoted.
+fn lua_key(name: &str) -> String {
+    if name::is_lua_identifier(name) {
+        name.to_string()
+    } else {
+        format!("[\"{}\"]", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render::Render;
+
+    #[test]
+    fn lua() -> anyhow::Result<()> {
+        let r#enum = super::Enum::from_parsed(
+            "ImGuiWindowFlags_".to_string(),
+            vec![
+                super::Value::from_parsed("ImGuiWindowFlags_None".to_string(), 0),
+                super::Value::from_parsed("ImGuiWindowFlags_NoTitleBar".to_string(), 1),
+                super::Value::from_parsed("ImGuiWindowFlags_NoResize".to_string(), 2),
+                super::Value::from_parsed("ImGuiWindowFlags_NoDecoration".to_string(), 3),
+                super::Value::from_parsed("ImGuiWindowFlags_3D".to_string(), 1),
+            ],
+        );
+
+        assert_eq!(
+            r#enum.lua(&[]),
+            indoc::indoc!(
+                r#"
+                gui.WindowFlags = {
+                    None = 0,
+                    NoTitleBar = 1,
+                    NoResize = 2,
+                    NoDecoration = 3,
+                    ["3D"] = 1,
+                }
+                gui.names.WindowFlags = {
+                    [0] = "None",
+                    [1] = "NoTitleBar",
+                    [2] = "NoResize",
+                    [3] = "NoDecoration",
+                }
+                "#
+            )
+        );
+        assert_eq!(r#enum.doc(&[]), "---@enum WindowFlags\n");
+        assert_eq!(
+            r#enum.teal(&[]),
+            indoc::indoc!(
+                r#"
+                record WindowFlags
+                    None: integer
+                    NoTitleBar: integer
+                    NoResize: integer
+                    NoDecoration: integer
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
 }
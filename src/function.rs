@@ -1,101 +1,799 @@
 use crate::{
+    c_type::CType,
+    comment, markdown,
     name::Name,
+    r#enum::Enum,
     r#type::{Type, TypeList},
-    render::Render,
+    render::{Annotation, Render},
 };
 use anyhow::{anyhow, Result};
 use indoc::indoc;
 use itertools::Itertools;
 
-/// Represents an ImGui function, not a method.
+/// Whether a function is a plain function or method, or creates or destroys a struct.
+#[derive(Debug, Clone)]
+pub enum Kind {
+    Function,
+    /// Allocates the struct, the destructor symbol frees it again.
+    Constructor {
+        destructor: Option<Name>,
+    },
+    Destructor,
+}
+
+/// The functions ImGui keeps the callbacks of to call them later in the frame or while rendering
+/// it, such as a draw list callback.
+const FRAME_CALLBACK_FUNCTIONS: [&str; 2] =
+    ["igSetNextWindowSizeConstraints", "ImDrawList_AddCallback"];
+
+/// The functions ImGui keeps the callbacks of for as long as the context exists, a Lua function
+/// can't be kept alive that long.
+const CONTEXT_CALLBACK_FUNCTIONS: [&str; 1] = ["igSetAllocatorFunctions"];
+
+/// The function starting a new frame, ImGui doesn't use the callbacks of the frames before it.
+const NEW_FRAME_FUNCTION: &str = "igNewFrame";
+
+/// Represents an ImGui function or method.
 #[derive(Debug)]
 pub struct Function {
     /// The full function name.
     name: Name,
+    /// The cimgui symbol this function calls.
+    cimgui_name: Name,
     /// All the arguments of this function.
     args: Vec<Arg>,
     /// The return value.
-    ret: Option<String>,
+    ret: Option<CType>,
     /// Where the source file lives.
     location: Option<(String, i64)>,
-    /// The C arguments signature.
-    signature: String,
+    /// The documentation from the comments in the ImGui header.
+    comment: Option<String>,
+    /// The title of the section of the ImGui header it's declared in.
+    section: Option<String>,
+    /// Whether this is a constructor, destructor or ordinary function.
+    kind: Kind,
+    /// The struct returned through the `pOut` argument of cimgui `nonUDT` variants.
+    out_ret: Option<CType>,
 }
 
 impl Function {
     /// Add a new function from the parsed data.
     pub fn from_parsed(
         name: Name,
-        args: Vec<Arg>,
+        cimgui_name: Name,
+        mut args: Vec<Arg>,
         location: Option<(String, i64)>,
-        ret: Option<String>,
-        signature: String,
+        ret: Option<CType>,
     ) -> Self {
-        Self {
+        // Arrays followed by their number of elements are passed as tables
+        for index in 1..args.len() {
+            if args[index - 1].is_counted_by(&args[index]) {
+                args[index - 1].count = Some(args[index].name.clone());
+            }
+        }
+
+        let mut func = Self {
             name,
+            cimgui_name,
             args,
             location,
+            comment: None,
+            section: None,
             ret,
-            signature,
+            kind: Kind::Function,
+            out_ret: None,
+        };
+
+        // Errors report the position of the argument in Lua, which skips the hidden arguments
+        let lua_indices = func
+            .args
+            .iter()
+            .map(|arg| {
+                func.lua_args()
+                    .iter()
+                    .position(|lua_arg| std::ptr::eq(*lua_arg, arg))
+            })
+            .collect::<Vec<_>>();
+        for (arg, lua_index) in func.args.iter_mut().zip(lua_indices) {
+            if let Some(lua_index) = lua_index {
+                arg.index = lua_index as u8 + 1;
+            }
         }
+
+        func
     }
-}
 
-impl Render for Function {
-    fn lua(&self, types: &Vec<Type>) -> String {
+    /// Return the struct written to the `pOut` argument that precedes the other arguments.
+    pub fn with_out_ret(mut self, out_ret: Option<CType>) -> Self {
+        self.out_ret = out_ret;
+        self
+    }
+
+    /// Document the function with the comments from the ImGui header.
+    pub fn with_comment(mut self, comment: Option<String>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Place the function in a section of the ImGui header, such as `Widgets: Main`.
+    pub fn with_section(mut self, section: Option<String>) -> Self {
+        self.section = section;
+        self
+    }
+
+    /// Mark the function as a constructor or destructor.
+    pub fn with_kind(mut self, kind: Kind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Whether this is a constructor, destructor or ordinary function.
+    pub fn kind(&self) -> &Kind {
+        &self.kind
+    }
+
+    /// The symbol freeing the object when this is a constructor.
+    fn destructor(&self) -> Option<&Name> {
+        match &self.kind {
+            Kind::Constructor { destructor } => destructor.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The documentation from the comments in the ImGui header.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// The title of the section of the ImGui header it's declared in.
+    pub fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    /// The type of the value returned by ImGui.
+    pub fn ret(&self) -> Option<&CType> {
+        self.ret.as_ref()
+    }
+
+    /// The header and line it's declared at.
+    pub fn location(&self) -> Option<&(String, i64)> {
+        self.location.as_ref()
+    }
+
+    /// The function name.
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    /// The cimgui symbol this function calls.
+    pub fn cimgui_name(&self) -> &Name {
+        &self.cimgui_name
+    }
+
+    /// The name with the suffix cimgui gives the overload, such as `MenuItemBoolPtr`.
+    pub fn overload_name(&self) -> Name {
+        let cimgui_name = self.cimgui_name.imgui();
+        cimgui_name
+            .rfind(self.name.imgui())
+            .map_or(cimgui_name, |start| &cimgui_name[start..])
+            .into()
+    }
+
+    /// The arguments passed from Lua, without the ones the wrapper fills in itself.
+    pub fn lua_args(&self) -> Vec<&Arg> {
+        let text_buffer_size = self.text_buffer_size();
+        self.args
+            .iter()
+            .filter(|arg| !text_buffer_size.is_some_and(|size| std::ptr::eq(size, *arg)))
+            .filter(|arg| {
+                !self
+                    .args
+                    .iter()
+                    .any(|array| array.count.as_deref() == Some(arg.name()))
+            })
+            .collect()
+    }
+
+    /// All C types used in the return value & arguments.
+    pub fn c_types(&self) -> impl Iterator<Item = &CType> {
+        self.ret
+            .iter()
+            .chain(self.out_ret.iter())
+            .chain(self.args.iter().map(|arg| &arg.r#type))
+    }
+
+    /// The Lua code calling the cimgui symbol and returning the converted result, followed by the
+    /// values written to the out-parameters.
+    fn call_string(&self, types: &[Type]) -> String {
+        let text_buffer = self.text_buffer();
+        let is_text_buffer = |arg: &Arg| text_buffer.is_some_and(|buf| std::ptr::eq(buf, arg));
+        // The callbacks of text inputs are called by the resize callback of the text buffer
+        let is_routed_callback = |arg: &Arg| text_buffer.is_some() && arg.is_callback(types);
+
+        // Values that are written to by ImGui are returned after the return value
+        let out_args = self
+            .args
+            .iter()
+            .filter(|arg| arg.is_out(types) || is_text_buffer(arg))
+            .collect::<Vec<_>>();
+        // Callbacks that ImGui only calls during the call can be freed right after it
+        let freed_callbacks = self
+            .args
+            .iter()
+            .filter(|arg| {
+                arg.is_callback(types) && !is_routed_callback(arg) && !self.keeps_frame_callbacks()
+            })
+            .collect::<Vec<_>>();
+
+        let call = format!(
+            "C.{symbol}({args})",
+            symbol = self.cimgui_name.imgui(),
+            args = self
+                .out_ret
+                .as_ref()
+                .map(|_| "pOut".to_string())
+                .into_iter()
+                .chain(self.args.iter().map(|arg| {
+                    if is_routed_callback(arg) {
+                        "text_buffer_callback".to_string()
+                    } else if arg.has_storage(types) || is_text_buffer(arg) {
+                        arg.out_name()
+                    } else if text_buffer.is_some() && arg.is_input_text_flags(types) {
+                        // Needed to grow the text buffer when the text doesn't fit anymore
+                        format!("bit.bor({}, gui.InputTextFlags.CallbackResize)", arg.name())
+                    } else if arg.is_ellipsis() {
+                        // Format in Lua so the values can never be misinterpreted by ImGui,
+                        // without values the text is passed as is so `%` doesn't need escaping
+                        let format = self.format_arg().map_or("", |format| format.name());
+                        format!(
+                            "select(\"#\", ...) == 0 and {format} or string.format({format}, ...)",
+                            format = format
+                        )
+                    } else if self
+                        .format_arg()
+                        .is_some_and(|format| std::ptr::eq(format, arg))
+                    {
+                        "\"%s\"".to_string()
+                    } else {
+                        arg.name().to_string()
+                    }
+                }))
+                .join(", ")
+        );
+
+        let (call, ret) = match self.ret.as_ref().map(|ret| ret.resolve(types)) {
+            // The struct is written to the allocated `pOut` instead of returned
+            _ if self.out_ret.is_some() => (
+                format!("    {}", call),
+                Some(
+                    match self
+                        .out_ret
+                        .as_ref()
+                        .and_then(|out_ret| vec_len(out_ret, types))
+                    {
+                        Some(_) => "vec_result(pOut)",
+                        None => "pOut",
+                    }
+                    .to_string(),
+                ),
+            ),
+            None => (format!("    {}", call), None),
+            Some(ret) if ret.is_void() => (format!("    {}", call), None),
+            // Strings need to be copied into Lua, NULL becomes nil
+            Some(ret) if ret.is_string() => (
+                format!("    local ret = {}", call),
+                Some("ret ~= nil and ffi.string(ret) or nil".to_string()),
+            ),
+            // Constructed objects are freed when they are garbage collected
+            Some(ret) if ret.is_indirect() && self.destructor().is_some() => (
+                format!("    local ret = {}", call),
+                Some(format!(
+                    "ffi.gc(ret, C.{})",
+                    self.destructor()
+                        .map_or("", |destructor| destructor.imgui())
+                )),
+            ),
+            // NULL pointers are truthy cdata objects, convert them to nil
+            Some(ret) if ret.is_indirect() => (
+                format!("    local ret = {}", call),
+                Some("ret ~= nil and ret or nil".to_string()),
+            ),
+            // Vectors are returned as cdata or tables depending on the configuration
+            Some(ret) if vec_len(&ret, types).is_some() => (
+                format!("    local ret = {}", call),
+                Some("vec_result(ret)".to_string()),
+            ),
+            // Nothing needs to happen after the call
+            Some(_) if out_args.is_empty() && freed_callbacks.is_empty() => {
+                (format!("    return {}", call), None)
+            }
+            Some(_) => (format!("    local ret = {}", call), Some("ret".to_string())),
+        };
+
+        let returns = ret
+            .into_iter()
+            .chain(out_args.iter().map(|arg| arg.name().to_string()))
+            .collect::<Vec<_>>();
+
+        self.out_ret
+            .iter()
+            .map(|out_ret| format!("    local pOut = ffi.new(\"{}\")", out_ret))
+            .chain(self.args.iter().filter_map(|arg| {
+                if is_text_buffer(arg) {
+                    Some(self.text_buffer_alloc_string(arg, types))
+                } else if is_routed_callback(arg) || !arg.has_storage(types) {
+                    None
+                } else if arg.is_callback(types) {
+                    Some(self.callback_alloc_string(arg))
+                } else {
+                    Some(arg.out_alloc_string(types))
+                }
+            }))
+            .chain(
+                (self.cimgui_name.imgui() == NEW_FRAME_FUNCTION)
+                    .then(|| "    release_frame_callbacks()".to_string()),
+            )
+            .chain(std::iter::once(call))
+            .chain(
+                freed_callbacks
+                    .iter()
+                    .map(|arg| format!("    free_callback({}, {})", arg.name(), arg.out_name())),
+            )
+            .chain(out_args.iter().map(|arg| {
+                if is_text_buffer(arg) {
+                    // The buffer might have been replaced by a bigger one while editing
+                    format!("    {} = text_buffer_result()", arg.name())
+                } else {
+                    arg.out_read_string(types)
+                }
+            }))
+            .chain(if returns.is_empty() {
+                None
+            } else {
+                Some(format!("    return {}", returns.join(", ")))
+            })
+            .join("\n")
+    }
+
+    /// Why the function can't be bound to Lua, `None` if it can.
+    pub fn unbindable_reason(&self, types: &[Type]) -> Option<String> {
+        if CONTEXT_CALLBACK_FUNCTIONS.contains(&self.cimgui_name.imgui()) {
+            return Some(
+                "ImGui keeps the callbacks for the lifetime of the context, which Lua functions \
+                 can't be kept alive for"
+                    .to_string(),
+            );
+        }
+
+        if let Some(arg) = self.args.iter().find(|arg| arg.r#type.is_va_list()) {
+            return Some(format!(
+                "argument \"{}\" is a va_list, which can't be constructed from LuaJIT",
+                arg.name
+            ));
+        }
+
+        // The same checks as the generated function, so generating it can't fail
+        self.lua_args()
+            .into_iter()
+            .find_map(|arg| arg.check_string(types, false).err())
+            .map(|err| err.to_string())
+    }
+
+    /// The `char* buf` of a `char* buf, size_t buf_size` pair, which is passed as a Lua string.
+    fn text_buffer(&self) -> Option<&Arg> {
+        self.args
+            .iter()
+            .tuple_windows()
+            .find(|(buf, size)| {
+                buf.r#type.is_string() && !buf.r#type.is_const() && size.r#type.is_size()
+            })
+            .map(|(buf, _)| buf)
+    }
+
+    /// The size of the text buffer, which is filled in by the wrapper.
+    fn text_buffer_size(&self) -> Option<&Arg> {
+        let buf = self.text_buffer()?;
+        self.args
+            .iter()
+            .skip_while(|arg| !std::ptr::eq(*arg, buf))
+            .nth(1)
+    }
+
+    /// Copy the Lua string into the shared, growable text buffer.
+    fn text_buffer_alloc_string(&self, arg: &Arg, types: &[Type]) -> String {
+        format!(
+            "    local {out}, {size} = text_buffer_arg({name}, {callback}, {index}, \"{name}\")",
+            out = arg.out_name(),
+            size = self.text_buffer_size().map_or("", |size| size.name()),
+            name = arg.name,
+            callback = self
+                .args
+                .iter()
+                .find(|arg| arg.is_callback(types))
+                .map_or("nil", |callback| callback.name()),
+            index = arg.index
+        )
+    }
+
+    /// Whether ImGui keeps the callbacks passed to this function to call them later in the frame.
+    fn keeps_frame_callbacks(&self) -> bool {
+        FRAME_CALLBACK_FUNCTIONS.contains(&self.cimgui_name.imgui())
+    }
+
+    /// Cast the Lua function of a callback argument to a C function pointer.
+    fn callback_alloc_string(&self, arg: &Arg) -> String {
+        if self.keeps_frame_callbacks() {
+            // Kept alive until the frame after the last one the function was passed in
+            format!(
+                "    local {out} = frame_callback_arg({name}, \"{type}\", {index}, \"{name}\")",
+                out = arg.out_name(),
+                name = arg.name,
+                r#type = arg.r#type,
+                index = arg.index
+            )
+        } else {
+            format!(
+                "    local {out} = callback_arg({name}, \"{type}\", {index}, \"{name}\")",
+                out = arg.out_name(),
+                name = arg.name,
+                r#type = arg.r#type,
+                index = arg.index
+            )
+        }
+    }
+
+    /// The format string argument of a variadic function, the one right before the `...`.
+    pub fn format_arg(&self) -> Option<&Arg> {
+        match self.args.as_slice() {
+            [.., format, last] if last.is_ellipsis() => Some(format),
+            _ => None,
+        }
+    }
+
+    /// The last argument when it's an `ImVec2`, which can then also be passed as two numbers.
+    pub fn loose_vec2(&self, types: &[Type]) -> Option<&Arg> {
+        self.args.last().filter(|arg| arg.vec_len(types) == Some(2))
+    }
+
+    /// The parameters, with a `?` after the name when it can be omitted, and their types.
+    fn doc_params(&self, types: &[Type], annotation: Annotation) -> Vec<(String, String)> {
+        self.lua_args()
+            .into_iter()
+            .map(|arg| {
+                if arg.is_ellipsis() {
+                    ("...".to_string(), "any".to_string())
+                } else if arg.is_optional(types) {
+                    (format!("{}?", arg.name), arg.doc_type(types, annotation))
+                } else if arg.is_nullable(types) {
+                    (
+                        arg.name.clone(),
+                        annotation.nullable(&arg.doc_type(types, annotation)),
+                    )
+                } else {
+                    (arg.name.clone(), arg.doc_type(types, annotation))
+                }
+            })
+            .chain(
+                self.loose_vec2(types)
+                    .map(|arg| (format!("{}?", arg.loose_y_name()), "number".to_string())),
+            )
+            .collect()
+    }
+
+    /// The types of the return values, the values written by ImGui are named.
+    fn doc_returns(&self, types: &[Type], annotation: Annotation) -> Vec<(String, Option<&str>)> {
+        let ret = match (&self.out_ret, &self.ret) {
+            (Some(out_ret), _) => Some(doc_type(out_ret, types, annotation)),
+            (None, Some(ret)) if !ret.is_void() => Some(doc_type(ret, types, annotation)),
+            _ => None,
+        };
+        let text_buffer = self.text_buffer();
+
+        ret.map(|ret| (ret, None))
+            .into_iter()
+            .chain(
+                self.args
+                    .iter()
+                    .filter(|arg| {
+                        arg.is_out(types) || text_buffer.is_some_and(|buf| std::ptr::eq(buf, *arg))
+                    })
+                    .map(|arg| (arg.doc_type(types, annotation), Some(arg.name()))),
+            )
+            .collect()
+    }
+
+    /// The LuaLS function type, such as `fun(label: string): boolean`.
+    pub fn doc_signature(&self, types: &[Type]) -> String {
+        self.signature_string("fun", types, Annotation::LuaLs)
+    }
+
+    /// The Teal function type, such as `function(label: string): boolean`.
+    pub fn teal_signature(&self, types: &[Type]) -> String {
+        self.signature_string("function", types, Annotation::Teal)
+    }
+
+    /// The Teal function types of a scope helper calling this as its begin function. The body
+    /// comes after the arguments, so there's one for every number of optional arguments passed
+    /// and the values to format are left out.
+    pub fn teal_scoped_signatures(&self, types: &[Type]) -> Vec<String> {
+        let params = self
+            .doc_params(types, Annotation::Teal)
+            .into_iter()
+            .filter(|(name, _)| name != "...")
+            .collect::<Vec<_>>();
+        let required = params
+            .iter()
+            .rposition(|(name, _)| !name.ends_with('?'))
+            .map_or(0, |index| index + 1);
+
+        (required..=params.len())
+            .map(|len| {
+                format!(
+                    "function({params}){returns}",
+                    params = params[..len]
+                        .iter()
+                        .map(|(name, r#type)| format!("{}: {}", name.trim_end_matches('?'), r#type))
+                        .chain(std::iter::once("body: function()".to_string()))
+                        .join(", "),
+                    returns = self.returns_string(types, Annotation::Teal)
+                )
+            })
+            .collect()
+    }
+
+    /// The function type with the keyword of the annotation language.
+    fn signature_string(&self, keyword: &str, types: &[Type], annotation: Annotation) -> String {
+        format!(
+            "{keyword}({params}){returns}",
+            keyword = keyword,
+            params = self
+                .doc_params(types, annotation)
+                .into_iter()
+                .map(|(name, r#type)| format!("{}: {}", name, r#type))
+                .join(", "),
+            returns = self.returns_string(types, annotation)
+        )
+    }
+
+    /// The return types after the parameters of a function type, empty without return values.
+    fn returns_string(&self, types: &[Type], annotation: Annotation) -> String {
+        let returns = self.doc_returns(types, annotation);
+
+        if returns.is_empty() {
+            String::new()
+        } else {
+            format!(
+                ": {}",
+                returns.into_iter().map(|(r#type, _)| r#type).join(", ")
+            )
+        }
+    }
+
+    /// The Markdown reference entry with the Lua signature, the parameters & return values.
+    pub fn markdown(&self, path: &str, types: &[Type], imgui_url: &str) -> String {
+        let params = self.doc_params(types, Annotation::LuaLs);
+        let returns = self.doc_returns(types, Annotation::LuaLs);
+        // The loose `y` of a vector is the last parameter and has no argument
+        let defaults = self
+            .lua_args()
+            .into_iter()
+            .map(|arg| arg.default_value.as_deref())
+            .chain(std::iter::repeat(None));
+
+        let mut entry = format!(
+            "### `{}({})`\n\nCalls `{}`",
+            path,
+            params.iter().map(|(name, _)| name).join(", "),
+            self.cimgui_name.imgui()
+        );
+        if let Some(location) = &self.location {
+            entry += &format!(" from {}", markdown::location_link(location, imgui_url));
+        }
+        entry += ".\n";
+        if let Some(comment) = &self.comment {
+            entry += &format!("\n{}", markdown::paragraph(comment));
+        }
+
+        if !params.is_empty() {
+            entry += &format!(
+                "\n{}",
+                markdown::table(
+                    &["Parameter", "Type", "Default"],
+                    &params
+                        .iter()
+                        .zip(defaults)
+                        .map(|((name, r#type), default)| {
+                            vec![
+                                markdown::code(name.trim_end_matches('?')),
+                                markdown::code(r#type),
+                                markdown::code(default.unwrap_or("")),
+                            ]
+                        })
+                        .collect::<Vec<_>>()
+                )
+            );
+        }
+        if !returns.is_empty() {
+            entry += &format!(
+                "\n{}",
+                markdown::table(
+                    &["Returns", "Type"],
+                    &returns
+                        .iter()
+                        .map(|(r#type, name)| {
+                            vec![markdown::code(name.unwrap_or("")), markdown::code(r#type)]
+                        })
+                        .collect::<Vec<_>>()
+                )
+            );
+        }
+
+        entry
+    }
+
+    /// The Lua function definition assigned to the path, such as `gui.begin`.
+    pub fn lua_function(&self, path: &str, types: &[Type]) -> String {
+        let loose_vec2 = self.loose_vec2(types);
+
         format!(
             indoc!(
                 r#"
-        function {module}.{name}({args})
-        {checks}
-            -- call
-            {ret}
+        function {path}({args})
+        {body}
         end
         "#
             ),
-            // TODO: Make this configurable
-            module = "gui",
-            name = self.name.lua(),
+            path = path,
             args = self
-                .args
-                .iter()
-                .map(|arg| arg.name())
-                .intersperse(", ")
-                .collect::<String>(),
-            checks = self
-                .args
-                .iter()
-                .map(|arg| arg
-                    .check_string(types)
-                    .expect("Could not build argument check"))
-                .intersperse("\n".to_string())
-                .collect::<String>(),
-            ret = self.ret.as_ref().map(|ret| "return ret").unwrap_or("")
+                .lua_args()
+                .into_iter()
+                .map(|arg| arg.name().to_string())
+                .chain(loose_vec2.map(|arg| arg.loose_y_name()))
+                .join(", "),
+            body = self
+                .lua_args()
+                .into_iter()
+                .filter_map(|arg| arg
+                    .check_string(
+                        types,
+                        loose_vec2.is_some_and(|last| std::ptr::eq(last, arg))
+                    )
+                    .expect("Functions with unchecked arguments are skipped by the parser"))
+                .chain(std::iter::once(self.call_string(types)))
+                .join("\n")
         )
     }
+}
+
+impl Render for Function {
+    fn lua(&self, types: &[Type]) -> String {
+        // TODO: Make this configurable
+        self.lua_function(&format!("{}.{}", "gui", self.name.lua()), types)
+    }
+
+    /// Get the LuaLS annotations of the parameters & return values.
+    fn doc(&self, types: &[Type]) -> String {
+        std::iter::once(comment::prefixed(self.comment(), "---"))
+            .chain(
+                self.doc_params(types, Annotation::LuaLs)
+                    .into_iter()
+                    .map(|(name, r#type)| format!("---@param {} {}\n", name, r#type)),
+            )
+            .chain(
+                self.doc_returns(types, Annotation::LuaLs)
+                    .into_iter()
+                    .map(|(r#type, name)| match name {
+                        Some(name) => format!("---@return {} {}\n", r#type, name),
+                        None => format!("---@return {}\n", r#type),
+                    }),
+            )
+            .collect()
+    }
 
-    fn doc(&self, _types: &Vec<Type>) -> String {
-        format!("")
+    /// Get the Teal declaration of the function as a record field.
+    fn teal(&self, types: &[Type]) -> String {
+        format!(
+            "{}{}: {}\n",
+            comment::prefixed(self.comment(), "--"),
+            self.name.lua(),
+            self.teal_signature(types)
+        )
     }
 
     /// Get the cdef definition of this function.
-    fn cdef(&self, _types: &Vec<Type>) -> String {
+    fn cdef(&self, _types: &[Type]) -> String {
+        // The `pOut` of nonUDT variants is the first argument
+        let args = self
+            .out_ret
+            .iter()
+            .map(|out_ret| format!("{}* pOut", out_ret))
+            .chain(
+                self.args
+                    .iter()
+                    .map(|arg| arg.r#type.declaration(&arg.name)),
+            )
+            .collect::<Vec<_>>();
+
         format!(
-            "{} {}{};",
-            self.ret.as_ref().unwrap_or(&"void".to_string()),
-            self.name.imgui(),
-            self.signature
+            "{} {}({});",
+            self.ret
+                .as_ref()
+                .map_or("void".to_string(), |ret| ret.to_string()),
+            self.cimgui_name.imgui(),
+            if args.is_empty() {
+                "void".to_string()
+            } else {
+                args.join(", ")
+            }
         )
     }
 }
 
+/// Whether values of the type can be converted from a plain Lua number or boolean.
+fn is_number_or_boolean(c_type: &CType, types: &[Type]) -> bool {
+    matches!(
+        c_type.lua_type(types).as_deref(),
+        Ok("number") | Ok("boolean")
+    )
+}
+
+/// The type of values of the C type, structs are named after their Lua class.
+pub fn doc_type(c_type: &CType, types: &[Type], annotation: Annotation) -> String {
+    let resolved = c_type.resolve(types);
+    if resolved.is_function_pointer() {
+        return "function".to_string();
+    }
+
+    let class = |c_type: &CType| match c_type.name().map(|name| types.find(name)) {
+        Some(Ok(Type::Struct(r#struct))) => Some(r#struct.name().lua_type().to_string()),
+        _ => None,
+    };
+    // Pointers to a single struct are cdata of the struct
+    let class = if resolved.is_indirect() {
+        resolved
+            .pointee()
+            .filter(|pointee| !pointee.is_indirect())
+            .and_then(|pointee| class(&pointee))
+    } else {
+        class(&resolved)
+    };
+    if let Some(class) = class {
+        return class;
+    }
+
+    match resolved.lua_type(types).as_deref() {
+        Ok("number") => "number",
+        Ok("boolean") => "boolean",
+        Ok("string") => "string",
+        _ => annotation.cdata(),
+    }
+    .to_string()
+}
+
+/// The number of components when the type is an `ImVec2` or `ImVec4` passed by value.
+fn vec_len(c_type: &CType, types: &[Type]) -> Option<usize> {
+    let resolved = c_type.resolve(types);
+    if resolved.is_indirect() {
+        return None;
+    }
+
+    match resolved.name() {
+        Some("ImVec2") => Some(2),
+        Some("ImVec4") => Some(4),
+        _ => None,
+    }
+}
+
 /// Represent an ImGui function & method argument.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Arg {
     name: String,
+    /// The default value as a Lua expression.
     default_value: Option<String>,
-    r#type: String,
+    r#type: CType,
     index: u8,
+    /// The name of the argument holding the number of elements, for arrays passed as tables.
+    count: Option<String>,
 }
 
 impl Arg {
@@ -103,7 +801,7 @@ impl Arg {
     pub fn from_parsed(
         name: String,
         default_value: Option<String>,
-        r#type: String,
+        r#type: CType,
         index: u8,
     ) -> Self {
         Self {
@@ -111,6 +809,7 @@ impl Arg {
             default_value,
             r#type,
             index,
+            count: None,
         }
     }
 
@@ -119,72 +818,415 @@ impl Arg {
         &self.name
     }
 
-    /// Get the type reference of the argument.
-    pub fn r#type<'a>(&'a self, types: &'a Vec<Type>) -> Result<&'a Type> {
-        types
-            .find(&self.r#type)
-            .map_err(|err| anyhow!("Could not get type of argument \"{}\": {}", self.name, err))
+    /// Whether the argument can be omitted.
+    pub fn has_default(&self) -> bool {
+        self.default_value.is_some()
     }
 
-    /// The Lua argument check.
-    pub fn check_string(&self, types: &Vec<Type>) -> Result<String> {
-        Ok(format!(
-            "    {name} = arg_check({name}, \"{type}\", {index})",
-            // Use 'name' or 'name or default_value'
-            name = self.default_value.as_ref().map_or(
-                self.name().to_string(),
-                |default_value| format!("{} or {}", self.name(), default_value)
-            ),
-            r#type = self.r#type(types)?.lua_primitive_type()?,
-            index = self.index
-        ))
+    /// The Lua type of the argument if it can be determined, out-parameters take the type of the
+    /// value they point to and arrays are passed as tables.
+    pub fn lua_type(&self, types: &[Type]) -> Option<String> {
+        if self.array_len(types).is_some() || self.count.is_some() {
+            return Some("table".to_string());
+        }
+        if self.is_callback(types) {
+            return Some("function".to_string());
+        }
+
+        self.out_type(types)
+            .unwrap_or_else(|| self.r#type.clone())
+            .lua_type(types)
+            .ok()
+    }
+
+    /// The type of the values accepted for this argument.
+    pub fn doc_type(&self, types: &[Type], annotation: Annotation) -> String {
+        if self.flags_enum(types).is_some() {
+            return annotation.union(&["integer", "string", &annotation.array("string")]);
+        }
+        if self.count.is_some() || self.array_len(types).is_some() {
+            return annotation.array(&self.r#type.pointee().map_or("any".to_string(), |element| {
+                doc_type(&element, types, annotation)
+            }));
+        }
+
+        let r#type = doc_type(
+            &self.out_type(types).unwrap_or_else(|| self.r#type.clone()),
+            types,
+            annotation,
+        );
+        match (self.vec_len(types), annotation) {
+            // The Teal records of vectors also accept arrays
+            (Some(_), Annotation::LuaLs) => {
+                annotation.union(&[&r#type, &annotation.array("number")])
+            }
+            _ => r#type,
+        }
+    }
+
+    /// The type ImGui writes back to when this is a pointer to a single number or boolean, or
+    /// any pointer named `out_*` such as the `unsigned char** out_pixels` of a texture.
+    pub fn out_type(&self, types: &[Type]) -> Option<CType> {
+        // Non-const char pointers are text buffers
+        if self.r#type.is_string() || self.r#type.is_const() || self.r#type.is_array() {
+            return None;
+        }
+
+        self.r#type.pointee().filter(|pointee| {
+            if pointee.is_indirect() {
+                self.is_named_out() && !pointee.is_array()
+            } else {
+                is_number_or_boolean(pointee, types)
+            }
+        })
+    }
+
+    /// Whether the name marks an argument only written by ImGui, such as `out_width`.
+    fn is_named_out(&self) -> bool {
+        self.name.starts_with("out_")
+    }
+
+    /// Whether ImGui only writes to this argument without reading it, the storage is then
+    /// allocated even when the Lua value is omitted.
+    fn is_pure_out(&self, types: &[Type]) -> bool {
+        self.is_named_out() && self.out_type(types).is_some()
+    }
+
+    /// The number of elements when this is a fixed-size array of numbers or booleans.
+    pub fn array_len(&self, types: &[Type]) -> Option<usize> {
+        self.r#type.array_len().filter(|_| {
+            self.r#type
+                .pointee()
+                .is_some_and(|element| is_number_or_boolean(&element, types))
+        })
+    }
+
+    /// Whether this is an input array of which the number of elements is the next argument, such
+    /// as `const char* const items[], int items_count`.
+    fn is_counted_by(&self, count: &Arg) -> bool {
+        self.r#type.is_indirect()
+            && self.r#type.is_const()
+            && !self.r#type.is_string()
+            && count.r#type.is_integer()
+            && count.name == format!("{}_count", self.name)
+    }
+
+    /// Whether this is the `...` of a variadic function.
+    pub fn is_ellipsis(&self) -> bool {
+        self.r#type.is_ellipsis()
+    }
+
+    /// Whether ImGui writes a value back through this argument.
+    pub fn is_out(&self, types: &[Type]) -> bool {
+        self.out_type(types).is_some()
+            || (self.array_len(types).is_some() && !self.r#type.is_const())
+    }
+
+    /// Whether this is the `ImGuiInputTextFlags` of a text input.
+    fn is_input_text_flags(&self, types: &[Type]) -> bool {
+        self.flags_enum(types)
+            .is_some_and(|r#enum| r#enum.name() == "ImGuiInputTextFlags_")
+    }
+
+    /// Whether this is a function pointer that can be passed as a Lua function.
+    pub fn is_callback(&self, types: &[Type]) -> bool {
+        self.r#type.resolve(types).is_function_pointer()
+    }
+
+    /// Whether the Lua value is copied into FFI storage before being passed to ImGui.
+    fn has_storage(&self, types: &[Type]) -> bool {
+        self.out_type(types).is_some()
+            || self.array_len(types).is_some()
+            || self.count.is_some()
+            || self.is_callback(types)
+    }
+
+    /// The number of components when this is an `ImVec2` or `ImVec4` passed by value.
+    pub fn vec_len(&self, types: &[Type]) -> Option<usize> {
+        vec_len(&self.r#type, types)
+    }
+
+    /// The enum when this is a `*Flags` argument, which can also be passed as names.
+    pub fn flags_enum<'a>(&self, types: &'a [Type]) -> Option<&'a Enum> {
+        let resolved = self.r#type.resolve(types);
+        if resolved.is_indirect() {
+            return None;
+        }
+
+        match types.find(resolved.name()?) {
+            Ok(Type::Enum(r#enum)) if r#enum.name().ends_with("Flags_") => Some(r#enum),
+            _ => None,
+        }
+    }
+
+    /// The name of the extra Lua argument holding `y` when an `ImVec2` is passed as two numbers.
+    fn loose_y_name(&self) -> String {
+        format!("{}_y", self.name)
+    }
+
+    /// Assign the default value when the argument is omitted, `None` without a default.
+    fn default_string(&self) -> Option<String> {
+        // Compare with nil instead of using `or`, which would replace an explicit `false`
+        match self.default_value.as_deref() {
+            None | Some("nil") => None,
+            Some(default_value) => Some(format!(
+                "    if {name} == nil then\n        {name} = {default}\n    end",
+                name = self.name,
+                default = default_value
+            )),
+        }
+    }
+
+    /// Whether passing `NULL`, which is `nil` in Lua, is allowed. That's the case when it's the
+    /// default, for pure out-parameters and for data pointers ImGui passes on without reading them
+    /// such as `user_data`.
+    pub fn is_nullable(&self, types: &[Type]) -> bool {
+        self.default_value.as_deref() == Some("nil")
+            || self.is_pure_out(types)
+            || (self.name != "self"
+                && !self.has_storage(types)
+                && self.lua_type(types).as_deref() == Some("cdata"))
+    }
+
+    /// Whether the argument can be left out of the call, because it has a default or ImGui only
+    /// writes to it. Other nullable arguments need an explicit `nil`.
+    pub fn is_optional(&self, types: &[Type]) -> bool {
+        self.has_default() || self.is_pure_out(types)
+    }
+
+    /// The name of the storage passed to ImGui instead of the Lua value.
+    fn out_name(&self) -> String {
+        format!("{}_out", self.name)
+    }
+
+    /// Allocate the storage for an out-parameter or array, initialized with the Lua value.
+    fn out_alloc_string(&self, types: &[Type]) -> String {
+        if let Some(count) = &self.count {
+            return format!(
+                "    local {}, {} = array_count_arg({}, \"{}[?]\")",
+                self.out_name(),
+                count,
+                self.name,
+                self.r#type
+                    .pointee()
+                    .map_or(String::new(), |element| element.to_string())
+            );
+        }
+        if self.array_len(types).is_some() {
+            return format!(
+                "    local {} = array_arg({}, \"{}\")",
+                self.out_name(),
+                self.name,
+                self.r#type
+            );
+        }
+
+        let out_type = self
+            .out_type(types)
+            .map_or_else(String::new, |out_type| out_type.to_string());
+        let alloc = format!("ffi.new(\"{}[1]\", {})", out_type, self.name);
+
+        if self.is_pure_out(types) {
+            // ImGui doesn't read the value, so the storage is zeroed when it's omitted
+            format!(
+                "    local {out} = {name} == nil and ffi.new(\"{type}[1]\") or {alloc}",
+                out = self.out_name(),
+                name = self.name,
+                r#type = out_type,
+                alloc = alloc
+            )
+        } else if self.is_nullable(types) {
+            format!(
+                "    local {out} = {name} ~= nil and {alloc} or nil",
+                out = self.out_name(),
+                name = self.name,
+                alloc = alloc
+            )
+        } else {
+            format!("    local {} = {}", self.out_name(), alloc)
+        }
+    }
+
+    /// Read the value ImGui wrote back to an out-parameter or array.
+    fn out_read_string(&self, types: &[Type]) -> String {
+        if let Some(len) = self.array_len(types) {
+            format!(
+                "    {name} = array_result({name}, {out}, {len})",
+                name = self.name,
+                out = self.out_name(),
+                len = len
+            )
+        } else if self.is_nullable(types) && !self.is_pure_out(types) {
+            format!(
+                "    if {out} ~= nil then\n        {name} = {out}[0]\n    end",
+                out = self.out_name(),
+                name = self.name
+            )
+        } else {
+            format!("    {} = {}[0]", self.name, self.out_name())
+        }
+    }
+
+    /// The Lua argument check preceded by its default value, `None` when the argument is checked
+    /// while it's converted.
+    pub fn check_string(&self, types: &[Type], loose_vec2: bool) -> Result<Option<String>> {
+        let check = self.type_check_string(types, loose_vec2)?;
+
+        Ok(match self.default_string() {
+            Some(default) => Some(match check {
+                Some(check) => format!("{}\n{}", default, check),
+                None => default,
+            }),
+            None => check,
+        })
+    }
+
+    /// The Lua argument check of the type, without the default value.
+    fn type_check_string(&self, types: &[Type], loose_vec2: bool) -> Result<Option<String>> {
+        // The values of `...` are checked by `string.format` & callbacks by `callback_arg`
+        if self.is_ellipsis() || self.is_callback(types) {
+            return Ok(None);
+        }
+
+        // Vectors are converted from tables, loose numbers or passed as cdata
+        match self.vec_len(types) {
+            Some(2) => {
+                return Ok(Some(format!(
+                    "    {name} = vec2_arg({name}, {y}, {index}, \"{name}\")",
+                    name = self.name,
+                    y = if loose_vec2 {
+                        self.loose_y_name()
+                    } else {
+                        "nil".to_string()
+                    },
+                    index = self.index
+                )))
+            }
+            Some(_) => {
+                return Ok(Some(format!(
+                    "    {name} = vec4_arg({name}, {index}, \"{name}\")",
+                    name = self.name,
+                    index = self.index
+                )))
+            }
+            None => (),
+        }
+
+        // Flags can be numbers, names or tables of names
+        if let Some(r#enum) = self.flags_enum(types) {
+            return Ok(Some(format!(
+                "    {name} = flags_arg({name}, gui.{enum}, {index}, \"{name}\")",
+                name = self.name,
+                r#enum = r#enum.lua_name(),
+                index = self.index
+            )));
+        }
+
+        // The number of elements is taken from the table
+        if self.count.is_some() {
+            return Ok(Some(format!(
+                "    {name} = args_check({name}, \"table\", {index}, \"{name}\")",
+                name = self.name,
+                index = self.index
+            )));
+        }
+
+        // Arrays can be tables or cdata, but tables need to have the right length
+        if let Some(len) = self.array_len(types) {
+            return Ok(Some(format!(
+                "    {name} = array_check({name}, {len}, {index}, \"{name}\")",
+                name = self.name,
+                len = len,
+                index = self.index
+            )));
+        }
+
+        let r#type = self
+            .out_type(types)
+            .unwrap_or_else(|| self.r#type.clone())
+            .lua_type(types)
+            .map_err(|err| anyhow!("Could not get type of argument \"{}\": {}", self.name, err))?;
+
+        Ok(Some(format!(
+            "    {name} = args_check({name}, \"{type}\", {index}, \"{name}\"{allow_nil})",
+            name = self.name,
+            r#type = r#type,
+            index = self.index,
+            // Arguments that default to NULL can be omitted
+            allow_nil = if self.is_nullable(types) {
+                ", true"
+            } else {
+                ""
+            }
+        )))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::render::Render;
+    use crate::{c_type::CType, render::Render};
 
     #[test]
     fn cdef() -> anyhow::Result<()> {
-        let arg1 = super::Arg::from_parsed("first".to_string(), None, "char*".to_string(), 0);
-        let arg2 = super::Arg::from_parsed("second".to_string(), None, "int".to_string(), 1);
+        let arg1 = super::Arg::from_parsed("first".to_string(), None, CType::parse("char*")?, 0);
+        let arg2 = super::Arg::from_parsed("second".to_string(), None, CType::parse("int")?, 1);
 
         let func = super::Function::from_parsed(
             "func".into(),
+            "igFunc".into(),
             vec![arg1, arg2],
             None,
-            Some("const char[512]".to_string()),
-            "(char*, int)".to_string(),
+            Some(CType::parse("const char[512]")?),
+        );
+
+        assert_eq!(
+            func.cdef(&[]),
+            "const char[512] igFunc(char* first, int second);"
         );
 
-        assert_eq!(func.cdef(&vec![]), "const char[512] func(char*, int);");
+        // cimgui gives the C++ signature of const methods, such as `()const` without `self`
+        let method = super::Function::from_parsed(
+            "IsActive".into(),
+            "ImGuiTextFilter_IsActive".into(),
+            vec![super::Arg::from_parsed(
+                "self".to_string(),
+                None,
+                CType::parse("ImGuiTextFilter*")?,
+                1,
+            )],
+            None,
+            Some(CType::parse("bool")?),
+        );
+        assert_eq!(
+            method.cdef(&[]),
+            "bool ImGuiTextFilter_IsActive(ImGuiTextFilter* self);"
+        );
 
         Ok(())
     }
 
     #[test]
     fn lua() -> anyhow::Result<()> {
-        let arg1 = super::Arg::from_parsed("first".to_string(), None, "char*".to_string(), 1);
-        let arg2 = super::Arg::from_parsed("second".to_string(), None, "int".to_string(), 2);
+        let arg1 = super::Arg::from_parsed("first".to_string(), None, CType::parse("char*")?, 1);
+        let arg2 = super::Arg::from_parsed("second".to_string(), None, CType::parse("int")?, 2);
 
         let func = super::Function::from_parsed(
             "func".into(),
+            "igFunc".into(),
             vec![arg1, arg2],
             None,
-            Some("const char[512]".to_string()),
-            "(char*, int)".to_string(),
+            Some(CType::parse("const char[512]")?),
         );
 
         assert_eq!(
-            func.lua(&super::Type::default_list()),
+            func.lua(&[]),
             indoc::indoc!(
                 r#"
                 function gui.func(first, second)
-                    first = arg_check(first, "string", 1)
-                    second = arg_check(second, "number", 2)
-                    -- call
-                    return ret
+                    first = args_check(first, "string", 1, "first")
+                    second = args_check(second, "number", 2, "second")
+                    local ret = C.igFunc(first, second)
+                    return ret ~= nil and ret or nil
                 end
                 "#
             )
@@ -192,4 +1234,735 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn lua_string_return() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "GetVersion".into(),
+            "igGetVersion".into(),
+            vec![],
+            None,
+            Some(CType::parse("const char*")?),
+        );
+
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.get_version()
+                    local ret = C.igGetVersion()
+                    return ret ~= nil and ffi.string(ret) or nil
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_out_args() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "Begin".into(),
+            "igBegin".into(),
+            vec![
+                super::Arg::from_parsed("name".to_string(), None, CType::parse("const char*")?, 1),
+                super::Arg::from_parsed(
+                    "p_open".to_string(),
+                    Some("nil".to_string()),
+                    CType::parse("bool*")?,
+                    2,
+                ),
+            ],
+            None,
+            Some(CType::parse("bool")?),
+        );
+
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.begin(name, p_open)
+                    name = args_check(name, "string", 1, "name")
+                    p_open = args_check(p_open, "boolean", 2, "p_open", true)
+                    local p_open_out = p_open ~= nil and ffi.new("bool[1]", p_open) or nil
+                    local ret = C.igBegin(name, p_open_out)
+                    if p_open_out ~= nil then
+                        p_open = p_open_out[0]
+                    end
+                    return ret, p_open
+                end
+                "#
+            )
+        );
+
+        let func = super::Function::from_parsed(
+            "SliderFloat".into(),
+            "igSliderFloat".into(),
+            vec![
+                super::Arg::from_parsed("label".to_string(), None, CType::parse("const char*")?, 1),
+                super::Arg::from_parsed("v".to_string(), None, CType::parse("float*")?, 2),
+            ],
+            None,
+            Some(CType::parse("bool")?),
+        );
+
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.slider_float(label, v)
+                    label = args_check(label, "string", 1, "label")
+                    v = args_check(v, "number", 2, "v")
+                    local v_out = ffi.new("float[1]", v)
+                    local ret = C.igSliderFloat(label, v_out)
+                    v = v_out[0]
+                    return ret, v
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_array_args() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "ColorEdit3".into(),
+            "igColorEdit3".into(),
+            vec![
+                super::Arg::from_parsed("label".to_string(), None, CType::parse("const char*")?, 1),
+                super::Arg::from_parsed("col".to_string(), None, CType::parse("float[3]")?, 2),
+            ],
+            None,
+            Some(CType::parse("bool")?),
+        );
+
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.color_edit_3(label, col)
+                    label = args_check(label, "string", 1, "label")
+                    col = array_check(col, 3, 2, "col")
+                    local col_out = array_arg(col, "float[3]")
+                    local ret = C.igColorEdit3(label, col_out)
+                    col = array_result(col, col_out, 3)
+                    return ret, col
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_vec_args() -> anyhow::Result<()> {
+        use crate::{r#struct::Struct, r#type::Type};
+
+        let types = [
+            Type::Struct(Struct::from_parsed("ImVec2".into(), vec![])),
+            Type::Struct(Struct::from_parsed("ImVec4".into(), vec![])),
+        ];
+
+        let func = super::Function::from_parsed(
+            "Button".into(),
+            "igButton".into(),
+            vec![
+                super::Arg::from_parsed("label".to_string(), None, CType::parse("const char*")?, 1),
+                super::Arg::from_parsed(
+                    "size".to_string(),
+                    Some("ffi.new(\"ImVec2\", 0, 0)".to_string()),
+                    CType::parse("const ImVec2")?,
+                    2,
+                ),
+            ],
+            None,
+            Some(CType::parse("bool")?),
+        );
+
+        assert_eq!(
+            func.lua(&types),
+            indoc::indoc!(
+                r#"
+                function gui.button(label, size, size_y)
+                    label = args_check(label, "string", 1, "label")
+                    if size == nil then
+                        size = ffi.new("ImVec2", 0, 0)
+                    end
+                    size = vec2_arg(size, size_y, 2, "size")
+                    return C.igButton(label, size)
+                end
+                "#
+            )
+        );
+
+        let func = super::Function::from_parsed(
+            "GetStyleColorVec4".into(),
+            "igGetStyleColorVec4".into(),
+            vec![
+                super::Arg::from_parsed("col".to_string(), None, CType::parse("const ImVec4")?, 1),
+                super::Arg::from_parsed("index".to_string(), None, CType::parse("int")?, 2),
+            ],
+            None,
+            Some(CType::parse("ImVec4")?),
+        );
+
+        assert_eq!(
+            func.lua(&types),
+            indoc::indoc!(
+                r#"
+                function gui.get_style_color_vec_4(col, index)
+                    col = vec4_arg(col, 1, "col")
+                    index = args_check(index, "number", 2, "index")
+                    local ret = C.igGetStyleColorVec4(col, index)
+                    return vec_result(ret)
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_true_default() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "BeginMenu".into(),
+            "igBeginMenu".into(),
+            vec![
+                super::Arg::from_parsed("label".to_string(), None, CType::parse("const char*")?, 1),
+                super::Arg::from_parsed(
+                    "enabled".to_string(),
+                    Some("true".to_string()),
+                    CType::parse("bool")?,
+                    2,
+                ),
+            ],
+            None,
+            Some(CType::parse("bool")?),
+        );
+
+        // An explicit `false` must not be replaced by the default
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.begin_menu(label, enabled)
+                    label = args_check(label, "string", 1, "label")
+                    if enabled == nil then
+                        enabled = true
+                    end
+                    enabled = args_check(enabled, "boolean", 2, "enabled")
+                    return C.igBeginMenu(label, enabled)
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_flags_args() -> anyhow::Result<()> {
+        use crate::{r#enum::Enum, r#type::Type, typedef::Typedef};
+
+        let types = [
+            Type::Enum(Enum::from_parsed("ImGuiWindowFlags_".to_string(), vec![])),
+            Type::Typedef(Typedef::from_parsed(
+                "ImGuiWindowFlags".into(),
+                CType::parse("int")?,
+            )),
+        ];
+
+        let func = super::Function::from_parsed(
+            "BeginChild".into(),
+            "igBeginChild".into(),
+            vec![
+                super::Arg::from_parsed(
+                    "str_id".to_string(),
+                    None,
+                    CType::parse("const char*")?,
+                    1,
+                ),
+                super::Arg::from_parsed(
+                    "flags".to_string(),
+                    Some("0".to_string()),
+                    CType::parse("ImGuiWindowFlags")?,
+                    2,
+                ),
+            ],
+            None,
+            Some(CType::parse("bool")?),
+        );
+
+        assert_eq!(
+            func.lua(&types),
+            indoc::indoc!(
+                r#"
+                function gui.begin_child(str_id, flags)
+                    str_id = args_check(str_id, "string", 1, "str_id")
+                    if flags == nil then
+                        flags = 0
+                    end
+                    flags = flags_arg(flags, gui.WindowFlags, 2, "flags")
+                    return C.igBeginChild(str_id, flags)
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_non_udt() -> anyhow::Result<()> {
+        use crate::{r#struct::Struct, r#type::Type};
+
+        let types = [Type::Struct(Struct::from_parsed("ImVec2".into(), vec![]))];
+
+        let func = super::Function::from_parsed(
+            "GetCursorPos".into(),
+            "igGetCursorPos_nonUDT".into(),
+            vec![],
+            None,
+            Some(CType::parse("void")?),
+        )
+        .with_out_ret(Some(CType::parse("ImVec2")?));
+
+        assert_eq!(
+            func.cdef(&types),
+            "void igGetCursorPos_nonUDT(ImVec2* pOut);"
+        );
+        assert_eq!(
+            func.lua(&types),
+            indoc::indoc!(
+                r#"
+                function gui.get_cursor_pos()
+                    local pOut = ffi.new("ImVec2")
+                    C.igGetCursorPos_nonUDT(pOut)
+                    return vec_result(pOut)
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_format() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "LabelText".into(),
+            "igLabelText".into(),
+            vec![
+                super::Arg::from_parsed("label".to_string(), None, CType::parse("const char*")?, 1),
+                super::Arg::from_parsed("fmt".to_string(), None, CType::parse("const char*")?, 2),
+                super::Arg::from_parsed("...".to_string(), None, CType::parse("...")?, 3),
+            ],
+            None,
+            Some(CType::parse("void")?),
+        );
+
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r##"
+                function gui.label_text(label, fmt, ...)
+                    label = args_check(label, "string", 1, "label")
+                    fmt = args_check(fmt, "string", 2, "fmt")
+                    C.igLabelText(label, "%s", select("#", ...) == 0 and fmt or string.format(fmt, ...))
+                end
+                "##
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_callback() -> anyhow::Result<()> {
+        use crate::{r#type::Type, typedef::Typedef};
+
+        let types = [Type::Typedef(Typedef::from_parsed(
+            "ImGuiSizeCallback".into(),
+            CType::parse("void(*)(ImGuiSizeCallbackData* data);")?,
+        ))];
+
+        let func = super::Function::from_parsed(
+            "Combo".into(),
+            "igComboFnBoolPtr".into(),
+            vec![super::Arg::from_parsed(
+                "items_getter".to_string(),
+                None,
+                CType::parse("bool(*)(void* data,int idx,const char** out_text)")?,
+                1,
+            )],
+            None,
+            Some(CType::parse("bool")?),
+        );
+
+        assert_eq!(
+            func.lua(&types),
+            indoc::indoc!(
+                r#"
+                function gui.combo(items_getter)
+                    local items_getter_out = callback_arg(items_getter, "bool(*)(void* data,int idx,const char** out_text)", 1, "items_getter")
+                    local ret = C.igComboFnBoolPtr(items_getter_out)
+                    free_callback(items_getter, items_getter_out)
+                    return ret
+                end
+                "#
+            )
+        );
+
+        // The callback is called later in the frame, so it's kept alive
+        let func = super::Function::from_parsed(
+            "SetNextWindowSizeConstraints".into(),
+            "igSetNextWindowSizeConstraints".into(),
+            vec![super::Arg::from_parsed(
+                "custom_callback".to_string(),
+                None,
+                CType::parse("ImGuiSizeCallback")?,
+                1,
+            )],
+            None,
+            Some(CType::parse("void")?),
+        );
+
+        assert_eq!(
+            func.lua(&types),
+            indoc::indoc!(
+                r#"
+                function gui.set_next_window_size_constraints(custom_callback)
+                    local custom_callback_out = frame_callback_arg(custom_callback, "ImGuiSizeCallback", 1, "custom_callback")
+                    C.igSetNextWindowSizeConstraints(custom_callback_out)
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_new_frame() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "NewFrame".into(),
+            "igNewFrame".into(),
+            vec![],
+            None,
+            Some(CType::parse("void")?),
+        );
+
+        // The callbacks of the frames before are released first
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.new_frame()
+                    release_frame_callbacks()
+                    C.igNewFrame()
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_nullable_pointer() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "PushID".into(),
+            "igPushIDPtr".into(),
+            vec![super::Arg::from_parsed(
+                "ptr_id".to_string(),
+                None,
+                CType::parse("const void*")?,
+                1,
+            )],
+            None,
+            Some(CType::parse("void")?),
+        );
+
+        // Data pointers are passed on as they are, so they can be NULL
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.push_id(ptr_id)
+                    ptr_id = args_check(ptr_id, "cdata", 1, "ptr_id", true)
+                    C.igPushIDPtr(ptr_id)
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_pure_out() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "GetTexDataAsAlpha8".into(),
+            "ImFontAtlas_GetTexDataAsAlpha8".into(),
+            vec![
+                super::Arg::from_parsed(
+                    "out_pixels".to_string(),
+                    None,
+                    CType::parse("unsigned char**")?,
+                    1,
+                ),
+                super::Arg::from_parsed("out_width".to_string(), None, CType::parse("int*")?, 2),
+                super::Arg::from_parsed(
+                    "out_bytes_per_pixel".to_string(),
+                    Some("nil".to_string()),
+                    CType::parse("int*")?,
+                    3,
+                ),
+            ],
+            None,
+            Some(CType::parse("void")?),
+        );
+
+        // Values only written by ImGui can be omitted and are always returned
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.get_tex_data_as_alpha_8(out_pixels, out_width, out_bytes_per_pixel)
+                    out_pixels = args_check(out_pixels, "cdata", 1, "out_pixels", true)
+                    out_width = args_check(out_width, "number", 2, "out_width", true)
+                    out_bytes_per_pixel = args_check(out_bytes_per_pixel, "number", 3, "out_bytes_per_pixel", true)
+                    local out_pixels_out = out_pixels == nil and ffi.new("unsigned char*[1]") or ffi.new("unsigned char*[1]", out_pixels)
+                    local out_width_out = out_width == nil and ffi.new("int[1]") or ffi.new("int[1]", out_width)
+                    local out_bytes_per_pixel_out = out_bytes_per_pixel == nil and ffi.new("int[1]") or ffi.new("int[1]", out_bytes_per_pixel)
+                    C.ImFontAtlas_GetTexDataAsAlpha8(out_pixels_out, out_width_out, out_bytes_per_pixel_out)
+                    out_pixels = out_pixels_out[0]
+                    out_width = out_width_out[0]
+                    out_bytes_per_pixel = out_bytes_per_pixel_out[0]
+                    return out_pixels, out_width, out_bytes_per_pixel
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn unbindable() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "SetState".into(),
+            "igSetState".into(),
+            vec![super::Arg::from_parsed(
+                "state".to_string(),
+                None,
+                CType::parse("ImGuiUnknownState")?,
+                1,
+            )],
+            None,
+            Some(CType::parse("void")?),
+        );
+
+        // Arguments without a Lua type skip the function instead of failing to generate it
+        assert!(func
+            .unbindable_reason(&[])
+            .is_some_and(|reason| reason.contains("\"state\"")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_text_buffer() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "InputText".into(),
+            "igInputText".into(),
+            vec![
+                super::Arg::from_parsed("label".to_string(), None, CType::parse("const char*")?, 1),
+                super::Arg::from_parsed("buf".to_string(), None, CType::parse("char*")?, 2),
+                super::Arg::from_parsed("buf_size".to_string(), None, CType::parse("size_t")?, 3),
+                super::Arg::from_parsed("width".to_string(), None, CType::parse("int")?, 4),
+            ],
+            None,
+            Some(CType::parse("bool")?),
+        );
+
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.input_text(label, buf, width)
+                    label = args_check(label, "string", 1, "label")
+                    buf = args_check(buf, "string", 2, "buf")
+                    width = args_check(width, "number", 3, "width")
+                    local buf_out, buf_size = text_buffer_arg(buf, nil, 2, "buf")
+                    local ret = C.igInputText(label, buf_out, buf_size, width)
+                    buf = text_buffer_result()
+                    return ret, buf
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_counted_array() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "ListBox".into(),
+            "igListBoxStr_arr".into(),
+            vec![
+                super::Arg::from_parsed(
+                    "items".to_string(),
+                    None,
+                    CType::parse("const char* const[]")?,
+                    1,
+                ),
+                super::Arg::from_parsed("items_count".to_string(), None, CType::parse("int")?, 2),
+                super::Arg::from_parsed(
+                    "height_in_items".to_string(),
+                    Some("-1".to_string()),
+                    CType::parse("int")?,
+                    3,
+                ),
+            ],
+            None,
+            Some(CType::parse("bool")?),
+        );
+
+        assert_eq!(
+            func.lua(&[]),
+            indoc::indoc!(
+                r#"
+                function gui.list_box(items, height_in_items)
+                    items = args_check(items, "table", 1, "items")
+                    if height_in_items == nil then
+                        height_in_items = -1
+                    end
+                    height_in_items = args_check(height_in_items, "number", 2, "height_in_items")
+                    local items_out, items_count = array_count_arg(items, "const char* const[?]")
+                    return C.igListBoxStr_arr(items_out, items_count, height_in_items)
+                end
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn doc() -> anyhow::Result<()> {
+        use crate::{
+            r#struct::{Field, Struct},
+            r#type::Type,
+        };
+
+        let types = [Type::Struct(Struct::from_parsed(
+            "ImVec2".into(),
+            vec![Field::from_parsed(
+                "x".into(),
+                None,
+                None,
+                CType::parse("float")?,
+            )],
+        ))];
+
+        let func = super::Function::from_parsed(
+            "Begin".into(),
+            "igBegin".into(),
+            vec![
+                super::Arg::from_parsed("name".to_string(), None, CType::parse("const char*")?, 1),
+                super::Arg::from_parsed(
+                    "p_open".to_string(),
+                    Some("nil".to_string()),
+                    CType::parse("bool*")?,
+                    2,
+                ),
+                super::Arg::from_parsed("size".to_string(), None, CType::parse("ImVec2")?, 3),
+            ],
+            None,
+            Some(CType::parse("bool")?),
+        )
+        .with_comment(Some("push window to the stack".to_string()));
+
+        assert_eq!(
+            func.doc(&types),
+            indoc::indoc!(
+                r#"
+                --- push window to the stack
+                ---@param name string
+                ---@param p_open? boolean
+                ---@param size Vec2|number[]
+                ---@param size_y? number
+                ---@return boolean
+                ---@return boolean p_open
+                "#
+            )
+        );
+        assert_eq!(
+            func.doc_signature(&types),
+            "fun(name: string, p_open?: boolean, size: Vec2|number[], size_y?: number): boolean, boolean"
+        );
+        assert_eq!(
+            func.teal(&types),
+            "-- push window to the stack\nbegin: function(name: string, p_open?: boolean, size: Vec2, size_y?: number): boolean, boolean\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn markdown() -> anyhow::Result<()> {
+        let func = super::Function::from_parsed(
+            "Checkbox".into(),
+            "igCheckbox".into(),
+            vec![
+                super::Arg::from_parsed("label".to_string(), None, CType::parse("const char*")?, 1),
+                super::Arg::from_parsed("v".to_string(), None, CType::parse("bool*")?, 2),
+                super::Arg::from_parsed(
+                    "flags".to_string(),
+                    Some("0".to_string()),
+                    CType::parse("int")?,
+                    3,
+                ),
+            ],
+            Some(("imgui".to_string(), 300)),
+            Some(CType::parse("bool")?),
+        )
+        .with_comment(Some(
+            "Toggles the value pointed to by v when clicked\nsquare checkbox".to_string(),
+        ));
+
+        assert_eq!(
+            func.markdown("gui.checkbox", &[], "https://example.com"),
+            indoc::indoc!(
+                r#"
+                ### `gui.checkbox(label, v, flags?)`
+
+                Calls `igCheckbox` from [imgui.h:300](https://example.com/imgui.h#L300).
+
+                Toggles the value pointed to by v when clicked\
+                square checkbox
+
+                | Parameter | Type | Default |
+                | --- | --- | --- |
+                | `label` | `string` |  |
+                | `v` | `boolean` |  |
+                | `flags` | `number` | `0` |
+
+                | Returns | Type |
+                | --- | --- |
+                |  | `boolean` |
+                | `v` | `boolean` |
+                "#
+            )
+        );
+
+        Ok(())
+    }
 }
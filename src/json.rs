@@ -13,27 +13,43 @@ impl Definitions {
 
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct Definition {
-    pub args: String,
     #[serde(rename = "argsT")]
     pub args_t: Vec<ArgT>,
-    #[serde(rename = "argsoriginal")]
-    pub args_original: Option<String>,
-    pub call_args: String,
-    #[serde(rename = "cimguiname")]
-    pub cimgui_name: String,
+    #[serde(default)]
+    pub constructor: bool,
     pub defaults: HashMap<String, String>,
+    #[serde(default)]
+    pub destructor: bool,
     #[serde(rename = "funcname")]
     pub func_name: Option<String>,
     pub location: Option<Location>,
+    #[serde(rename = "nonUDT")]
+    pub non_udt: Option<i64>,
     #[serde(rename = "ov_cimguiname")]
     pub ov_cimgui_name: String,
     pub ret: Option<String>,
-    pub signature: String,
     #[serde(rename = "stname")]
     pub struct_name: String,
     #[serde(default)]
     pub templated: bool,
-    pub namespace: Option<String>,
+}
+
+impl Definition {
+    /// Whether this is the `nonUDT` variant of the other definition, with the struct return
+    /// value replaced by a `pOut` pointer argument.
+    pub fn replaces(&self, other: &Definition) -> bool {
+        self.non_udt.is_some()
+            && other.non_udt.is_none()
+            && self.func_name == other.func_name
+            && self.struct_name == other.struct_name
+            && self.args_t.len() == other.args_t.len() + 1
+            && self
+                .args_t
+                .iter()
+                .skip(1)
+                .zip(other.args_t.iter())
+                .all(|(arg, other_arg)| arg.r#type == other_arg.r#type)
+    }
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -64,7 +80,6 @@ pub struct Enum(pub Vec<EnumValue>);
 pub struct EnumValue {
     pub name: String,
     pub calc_value: Option<i64>,
-    pub value: String,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -87,6 +102,7 @@ pub struct Struct(pub Vec<Field>);
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct Field {
     pub name: String,
+    pub bitfield: Option<String>,
     pub template_type: Option<String>,
     pub r#type: String,
 }
@@ -0,0 +1,41 @@
+local function array_check(arg, length, arg_index, arg_name)
+	if type(arg) == "table" then
+		if #arg ~= length then
+			error(string.format("Argument #%d %q must have %d elements, but got %d",
+				arg_index, arg_name, length, #arg), 3)
+		end
+	elseif type(arg) ~= "cdata" then
+		error(string.format("Argument #%d %q must be a table of %d elements, but got %s",
+			arg_index, arg_name, length, type(arg)), 3)
+	end
+
+	return arg
+end
+
+local function array_arg(arg, c_type)
+	if type(arg) == "cdata" then
+		return arg
+	end
+
+	return ffi.new(c_type, arg)
+end
+
+local function array_result(arg, array, length)
+	-- Cdata arrays are already written to by ImGui
+	if type(arg) == "cdata" then
+		return arg
+	end
+
+	local result = {}
+	for i = 1, length do
+		result[i] = array[i - 1]
+	end
+
+	return result
+end
+
+local function array_count_arg(arg, c_type)
+	-- The strings & numbers in the table are converted to C values
+	local length = #arg
+	return ffi.new(c_type, length, arg), length
+end
@@ -0,0 +1,70 @@
+local function callback_check(callback, arg_index, arg_name)
+	local callback_type = type(callback)
+	if callback_type ~= "function" and callback_type ~= "cdata" and callback_type ~= "nil" then
+		error(string.format("Argument #%d %q of function %q expected a function, but got %q",
+			arg_index, arg_name, debug.getinfo(3, "n").name or "?", callback_type), 4)
+	end
+end
+
+-- Every ffi.cast of a Lua function takes one of the few callback slots, so they must be freed
+local function callback_arg(callback, c_type, arg_index, arg_name)
+	callback_check(callback, arg_index, arg_name)
+	if type(callback) == "function" then
+		return ffi.cast(c_type, callback)
+	end
+
+	return callback
+end
+
+local function free_callback(callback, cdata)
+	-- Only the callbacks created from Lua functions are owned by the wrapper
+	if type(callback) == "function" then
+		cdata:free()
+	end
+end
+
+-- Callbacks ImGui calls later in the frame or while rendering it, by C type & Lua function. They
+-- are kept until the frame after the last one they were passed in
+local frame_callbacks = {}
+local previous_frame_callbacks = {}
+
+local function frame_callback_arg(callback, c_type, arg_index, arg_name)
+	callback_check(callback, arg_index, arg_name)
+	if type(callback) ~= "function" then
+		return callback
+	end
+
+	local callbacks = frame_callbacks[c_type]
+	if callbacks == nil then
+		callbacks = {}
+		frame_callbacks[c_type] = callbacks
+	end
+
+	local cdata = callbacks[callback]
+	if cdata == nil then
+		-- Passing the same function every frame reuses the callback
+		local previous = previous_frame_callbacks[c_type]
+		cdata = previous and previous[callback]
+		if cdata ~= nil then
+			previous[callback] = nil
+		else
+			cdata = ffi.cast(c_type, callback)
+		end
+		callbacks[callback] = cdata
+	end
+
+	return cdata
+end
+
+-- Called when a new frame starts, the callbacks that weren't passed during the last frame aren't
+-- referenced by ImGui anymore
+local function release_frame_callbacks()
+	for _, callbacks in pairs(previous_frame_callbacks) do
+		for _, cdata in pairs(callbacks) do
+			cdata:free()
+		end
+	end
+
+	previous_frame_callbacks = frame_callbacks
+	frame_callbacks = {}
+end
@@ -0,0 +1,66 @@
+local function flags_arg(flags, enum, arg_index, arg_name)
+	local flags_type = type(flags)
+	if flags_type == "number" then
+		return flags
+	elseif flags_type == "string" then
+		local value = enum[flags]
+		if value == nil then
+			error(string.format("Argument #%d %q has unknown flag %q", arg_index, arg_name, flags), 3)
+		end
+
+		return value
+	elseif flags_type == "table" then
+		local value = 0
+		for _, flag in ipairs(flags) do
+			local flag_value = type(flag) == "number" and flag or enum[flag]
+			if flag_value == nil then
+				error(string.format("Argument #%d %q has unknown flag %q", arg_index, arg_name, tostring(flag)), 3)
+			end
+
+			value = bit.bor(value, flag_value)
+		end
+
+		return value
+	end
+
+	error(string.format("Argument #%d %q must be a number, a flag name or a table of flag names, but got %s",
+		arg_index, arg_name, flags_type), 3)
+end
+
+-- Combine flags by their names, such as `gui.flags(gui.WindowFlags, "NoTitleBar", "NoResize")`
+function gui.flags(enum, ...)
+	local value = 0
+	for i = 1, select("#", ...) do
+		local flag = select(i, ...)
+		local flag_value = type(flag) == "number" and flag or enum[flag]
+		if flag_value == nil then
+			-- The enum is the first argument, the flags start at the second
+			error(string.format("Argument #%d %q has unknown flag %q", i + 1, "...", tostring(flag)), 2)
+		end
+
+		value = bit.bor(value, flag_value)
+	end
+
+	return value
+end
+
+-- The names of all single bit flags set in the value, ordered by their value
+function gui.flag_names(enum, value)
+	local flags = {}
+	for name, flag in pairs(enum) do
+		-- Skip the combined flags such as `NoDecoration`
+		if flag > 0 and bit.band(flag, flag - 1) == 0 and bit.band(value, flag) ~= 0 then
+			table.insert(flags, { name = name, flag = flag })
+		end
+	end
+	table.sort(flags, function(a, b)
+		return a.flag < b.flag
+	end)
+
+	local names = {}
+	for i, flag in ipairs(flags) do
+		names[i] = flag.name
+	end
+
+	return names
+end
@@ -0,0 +1,9 @@
+local function overload_error(name, ...)
+	local types = {}
+	for i = 1, select("#", ...) do
+		types[i] = type((select(i, ...)))
+	end
+
+	error(string.format("No overload of function %q matches the arguments (%s)",
+		name, table.concat(types, ", ")), 3)
+end
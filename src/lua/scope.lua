@@ -0,0 +1,37 @@
+local function is_scoped(...)
+	local n = select("#", ...)
+	return n > 0 and type((select(n, ...))) == "function"
+end
+
+local function scope_results(...)
+	return { n = select("#", ...), ... }
+end
+
+-- Call the function passed as the last argument between the begin & end functions, the end
+-- function is also called when the body errors so the ImGui stack stays intact
+local function scoped(name, begin, end_scope, end_call, ...)
+	if not is_scoped(...) then
+		error(string.format("The last argument of function %q must be a function", name), 2)
+	end
+
+	local n = select("#", ...)
+	local body = select(n, ...)
+	local args = { ... }
+	local results = scope_results(begin(unpack(args, 1, n - 1)))
+
+	-- "always" ends regardless of the result, "opened" only when begin returned true & "void"
+	-- begin functions don't return anything
+	local opened = end_call == "void" or results[1]
+	local ok, err = true, nil
+	if opened then
+		ok, err = pcall(body)
+	end
+	if opened or end_call == "always" then
+		end_scope()
+	end
+	if not ok then
+		error(err, 0)
+	end
+
+	return unpack(results, 1, results.n)
+end
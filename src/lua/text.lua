@@ -0,0 +1,52 @@
+-- One buffer is shared by all text inputs, ImGui only writes to it during the call
+local text_buffer = {
+	data = ffi.new("char[?]", 256),
+	size = 256,
+	callback = nil,
+}
+
+local function text_buffer_grow(size)
+	if size <= text_buffer.size then
+		return
+	end
+
+	-- Grow in steps so typing doesn't reallocate for every character
+	local new_size = math.max(size, text_buffer.size * 2)
+	local data = ffi.new("char[?]", new_size)
+	ffi.copy(data, text_buffer.data, text_buffer.size)
+	text_buffer.data = data
+	text_buffer.size = new_size
+end
+
+local function text_buffer_arg(text, callback, arg_index, arg_name)
+	callback_check(callback, arg_index, arg_name)
+
+	text_buffer_grow(#text + 1)
+	ffi.copy(text_buffer.data, text)
+	text_buffer.callback = callback
+
+	return text_buffer.data, text_buffer.size
+end
+
+-- Created once, the callback passed by the user is called from here
+local text_buffer_callback = ffi.cast("ImGuiInputTextCallback", function(data)
+	if data.EventFlag == gui.InputTextFlags.CallbackResize then
+		text_buffer_grow(data.BufSize)
+		data.Buf = text_buffer.data
+		data.BufSize = text_buffer.size
+		return 0
+	end
+
+	local callback = text_buffer.callback
+	if callback ~= nil then
+		return callback(data) or 0
+	end
+
+	return 0
+end)
+
+local function text_buffer_result()
+	text_buffer.callback = nil
+
+	return ffi.string(text_buffer.data)
+end
@@ -0,0 +1,41 @@
+local function vec2_arg(arg, y, arg_index, arg_name)
+	local arg_type = type(arg)
+	if arg_type == "cdata" then
+		return arg
+	elseif arg_type == "number" and type(y) == "number" then
+		return ffi.new("ImVec2", arg, y)
+	elseif arg_type == "table" then
+		local x, y = arg.x or arg[1], arg.y or arg[2]
+		if type(x) == "number" and type(y) == "number" then
+			return ffi.new("ImVec2", x, y)
+		end
+	end
+
+	error(string.format("Argument #%d %q must be an ImVec2, a table {x, y} or two numbers, but got %s",
+		arg_index, arg_name, arg_type), 3)
+end
+
+local function vec4_arg(arg, arg_index, arg_name)
+	local arg_type = type(arg)
+	if arg_type == "cdata" then
+		return arg
+	elseif arg_type == "table" then
+		local x, y, z, w = arg.x or arg[1], arg.y or arg[2], arg.z or arg[3], arg.w or arg[4]
+		if type(x) == "number" and type(y) == "number" and type(z) == "number" and type(w) == "number" then
+			return ffi.new("ImVec4", x, y, z, w)
+		end
+	end
+
+	error(string.format("Argument #%d %q must be an ImVec4 or a table {x, y, z, w}, but got %s",
+		arg_index, arg_name, arg_type), 3)
+end
+
+local function vec_result(vec)
+	if not gui.vec_as_table then
+		return vec
+	elseif ffi.istype("ImVec4", vec) then
+		return { x = vec.x, y = vec.y, z = vec.z, w = vec.w }
+	else
+		return { x = vec.x, y = vec.y }
+	end
+end
@@ -1,11 +1,18 @@
+mod c_type;
+mod comment;
+mod default;
 mod r#enum;
 mod function;
 mod json;
+mod markdown;
 mod name;
+mod overload;
 mod parser;
 mod render;
+mod scope;
 mod r#struct;
 mod r#type;
+mod typedef;
 
 use anyhow::Result;
 use clap::Clap;
@@ -21,6 +28,9 @@ struct Opts {
     output_directory: String,
     #[clap(short, long, default_value = "cimgui")]
     cimgui_directory: String,
+    /// The URL of the ImGui sources the reference links to
+    #[clap(long, default_value = "https://github.com/ocornut/imgui/blob/master")]
+    imgui_url: String,
 }
 
 fn main() -> Result<()> {
@@ -58,6 +68,19 @@ fn main() -> Result<()> {
         parser.add_json_typedefs(&contents)?;
     }
 
+    // Read the ImGui headers for the comments documenting the declarations
+    for path in glob(&format!("{}/imgui/**/*.h", opts.cimgui_directory))? {
+        let path = path?;
+        let mut file = File::open(&path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        // The locations refer to the headers without the extension
+        if let Some(filename) = path.file_stem().and_then(|stem| stem.to_str()) {
+            parser.add_header(filename, &contents);
+        }
+    }
+
     // Parse the data
     let data = parser.parse()?;
 
@@ -67,11 +90,27 @@ fn main() -> Result<()> {
     // Write the Lua to a file
     fs::write(format!("{}/gui.lua", &opts.output_directory), data.lua())?;
 
+    // Write the Teal declarations to a file
+    fs::write(format!("{}/gui.d.tl", &opts.output_directory), data.teal())?;
+
     // Write the cdefs to a file
     fs::write(
         format!("{}/cdefs.lua", &opts.output_directory),
         data.cdefs(),
     )?;
 
+    // Write the Markdown reference pages to a subdirectory
+    let reference_directory = format!("{}/reference", &opts.output_directory);
+    fs::create_dir_all(&reference_directory)?;
+    for (filename, page) in data.markdown(&opts.imgui_url) {
+        fs::write(format!("{}/{}", reference_directory, filename), page)?;
+    }
+
+    // Write the functions that couldn't be bound to a file
+    fs::write(
+        format!("{}/skipped.txt", &opts.output_directory),
+        data.skipped_report(),
+    )?;
+
     Ok(())
 }
@@ -0,0 +1,111 @@
+use itertools::Itertools;
+
+/// The path of an ImGui header from the root of the repository, the backends live in their own
+/// directory.
+pub fn header_path(filename: &str) -> String {
+    if filename.starts_with("imgui_impl_") {
+        format!("backends/{}.h", filename)
+    } else {
+        format!("{}.h", filename)
+    }
+}
+
+/// A link to the ImGui header, such as `[imgui.h](…/imgui.h)`.
+pub fn header_link(filename: &str, imgui_url: &str) -> String {
+    format!(
+        "[{path}]({url}/{path})",
+        path = header_path(filename),
+        url = imgui_url.trim_end_matches('/')
+    )
+}
+
+/// A link to the line in the ImGui header, such as `[imgui.h:300](…/imgui.h#L300)`.
+pub fn location_link(location: &(String, i64), imgui_url: &str) -> String {
+    let (filename, line_number) = location;
+
+    format!(
+        "[{path}:{line}]({url}/{path}#L{line})",
+        path = header_path(filename),
+        line = line_number,
+        url = imgui_url.trim_end_matches('/')
+    )
+}
+
+/// The file name of the reference page of a section of a header, such as
+/// `imgui-widgets-main.md` for `Widgets: Main` of imgui.h.
+pub fn page_filename(header: Option<&str>, section: Option<&str>) -> String {
+    let slug = section.map(|section| {
+        section
+            .to_lowercase()
+            .split(|char: char| !char.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .join("-")
+    });
+
+    match (header, slug) {
+        (Some(header), Some(slug)) => format!("{}-{}.md", header, slug),
+        (Some(header), None) => format!("{}.md", header),
+        (None, Some(slug)) => format!("{}.md", slug),
+        (None, None) => "functions.md".to_string(),
+    }
+}
+
+/// Inline code that can be used inside a table cell.
+pub fn code(text: &str) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("`{}`", text.replace('|', "\\|"))
+    }
+}
+
+/// A paragraph with the lines of a header comment, kept on separate lines with hard breaks.
+pub fn paragraph(comment: &str) -> String {
+    format!("{}\n", comment.lines().join("\\\n"))
+}
+
+/// A table with a header row, the cells must already be escaped.
+pub fn table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    std::iter::once(format!("| {} |", headers.join(" | ")))
+        .chain(std::iter::once(format!(
+            "|{}",
+            headers.iter().map(|_| " --- |").join("")
+        )))
+        .chain(rows.iter().map(|row| format!("| {} |", row.join(" | "))))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn table() -> anyhow::Result<()> {
+        assert_eq!(
+            super::table(
+                &["Name", "Type"],
+                &[vec![super::code("flags"), super::code("integer|string")]]
+            ),
+            indoc::indoc!(
+                r#"
+                | Name | Type |
+                | --- | --- |
+                | `flags` | `integer\|string` |
+                "#
+            )
+        );
+        assert_eq!(
+            super::location_link(&("imgui".to_string(), 300), "https://example.com/imgui/"),
+            "[imgui.h:300](https://example.com/imgui/imgui.h#L300)"
+        );
+        assert_eq!(
+            super::location_link(&("imgui_impl_opengl3".to_string(), 20), "https://example.com"),
+            "[backends/imgui_impl_opengl3.h:20](https://example.com/backends/imgui_impl_opengl3.h#L20)"
+        );
+        assert_eq!(
+            super::page_filename(Some("imgui"), Some("Widgets: Color Editor/Picker")),
+            "imgui-widgets-color-editor-picker.md"
+        );
+
+        Ok(())
+    }
+}
@@ -10,10 +10,44 @@ impl Name {
         &self.0
     }
 
-    /// The converted Lua representation.
+    /// The converted Lua representation, keywords such as `end` get a trailing underscore.
     pub fn lua(&self) -> String {
-        self.0.to_snake_case()
+        let name = self.0.to_snake_case();
+        if LUA_KEYWORDS.contains(&name.as_str()) {
+            format!("{}_", name)
+        } else {
+            name
+        }
     }
+
+    /// The Lua representation of a type name, such as `DrawList` for `ImDrawList`.
+    pub fn lua_type(&self) -> &str {
+        lua_type(&self.0)
+    }
+}
+
+/// Reserved words that can't be used as Lua identifiers.
+pub const LUA_KEYWORDS: [&str; 21] = [
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "if", "in", "local",
+    "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+/// Whether the name can be used as a Lua identifier without quoting it.
+pub fn is_lua_identifier(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !LUA_KEYWORDS.contains(&name)
+}
+
+/// Strip the `ImGui` or `Im` prefix & the trailing underscore of enums from a type name.
+pub fn lua_type(name: &str) -> &str {
+    let name = name.trim_end_matches('_');
+
+    name.strip_prefix("ImGui")
+        .or_else(|| name.strip_prefix("Im"))
+        .unwrap_or(name)
 }
 
 impl From<String> for Name {
@@ -0,0 +1,563 @@
+use crate::{comment, function::Function, name::Name, r#type::Type, render::Render};
+use indoc::indoc;
+use itertools::Itertools;
+
+/// All ImGui functions sharing the same Lua name, dispatched at runtime by their arguments.
+#[derive(Debug)]
+pub struct Overloads {
+    /// The shared function name.
+    name: Name,
+    /// All the overloaded variants, the most specific ones first.
+    functions: Vec<Function>,
+}
+
+impl Overloads {
+    /// Group functions that map to the same Lua name, the overloads that can't be told apart from
+    /// the ones before them get a name of their own.
+    pub fn group(functions: Vec<Function>, types: &[Type]) -> Vec<Self> {
+        functions
+            .into_iter()
+            .map(|func| (func.name().lua(), func))
+            .into_group_map()
+            .into_values()
+            .flat_map(|functions| Self::dispatch_order(functions, types))
+            .sorted_by_key(|overloads| overloads.name.lua())
+            .collect()
+    }
+
+    /// Order the functions in which the dispatcher tries them, the functions it could never
+    /// reach are split off with the overloaded name cimgui gives them.
+    fn dispatch_order(functions: Vec<Function>, types: &[Type]) -> Vec<Self> {
+        let name = functions[0].name().clone();
+
+        // Try the overloads with the least unknown argument types first
+        let functions = functions
+            .into_iter()
+            .sorted_by(|a, b| a.cimgui_name().imgui().cmp(b.cimgui_name().imgui()))
+            .sorted_by_key(|func| {
+                func.lua_args()
+                    .into_iter()
+                    .filter(|arg| arg.lua_type(types).is_none())
+                    .count()
+            });
+
+        let mut reachable: Vec<Function> = vec![];
+        let mut unreachable = vec![];
+        for func in functions {
+            if reachable
+                .iter()
+                .any(|earlier| Self::shadows(earlier, &func, types))
+            {
+                unreachable.push(Self {
+                    name: func.overload_name(),
+                    functions: vec![func],
+                });
+            } else {
+                reachable.push(func);
+            }
+        }
+
+        std::iter::once(Self {
+            name,
+            functions: reachable,
+        })
+        .chain(unreachable)
+        .collect()
+    }
+
+    /// The shared function name.
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    /// Where the first overload is declared, which the reference pages are sorted by.
+    pub fn location(&self) -> Option<&(String, i64)> {
+        self.functions.iter().find_map(|func| func.location())
+    }
+
+    /// The section of the ImGui header the first overload is declared in.
+    pub fn section(&self) -> Option<&str> {
+        self.functions.iter().find_map(|func| func.section())
+    }
+
+    /// The ImGui name this was split off from when the dispatcher could never reach it, such as
+    /// `MenuItem` for `MenuItemBoolPtr`.
+    pub fn split_from(&self) -> Option<&Name> {
+        self.functions
+            .first()
+            .map(|func| func.name())
+            .filter(|name| name.imgui() != self.name.imgui())
+    }
+
+    /// All the overloaded variants, in the order they're dispatched.
+    pub fn functions(&self) -> &[Function] {
+        &self.functions
+    }
+
+    /// The least & most number of values the function accepts, `None` when there's no limit.
+    fn arity(func: &Function, types: &[Type]) -> (usize, Option<usize>) {
+        let args = func.lua_args();
+        let required = args
+            .iter()
+            .rposition(|arg| !arg.is_optional(types) && !arg.is_ellipsis())
+            .map_or(0, |index| index + 1);
+
+        if func.format_arg().is_some() {
+            // The values to format can be of any amount
+            (required, None)
+        } else {
+            // The loose `y` of an `ImVec2` is an extra value
+            let loose_y = func.loose_vec2(types).map_or(0, |_| 1);
+            (required, Some(args.len() + loose_y))
+        }
+    }
+
+    /// The Lua types accepted for the value at the 0-based position, `None` when anything is
+    /// accepted. `nil` means the value can be omitted.
+    fn accepted_types(func: &Function, position: usize, types: &[Type]) -> Option<Vec<String>> {
+        let args = func.lua_args();
+        let loose_vec2 = func.loose_vec2(types);
+
+        match args.get(position) {
+            // The values to format can be anything, flags can be passed as many different types
+            Some(arg) if arg.is_ellipsis() || arg.flags_enum(types).is_some() => None,
+            Some(arg) => {
+                let mut accepted = if arg.vec_len(types).is_some() {
+                    // Vectors are converted from tables, a loose `ImVec2` also from two numbers
+                    let mut accepted = vec!["table".to_string(), "cdata".to_string()];
+                    if loose_vec2.is_some_and(|loose| std::ptr::eq(loose, *arg)) {
+                        accepted.push("number".to_string());
+                    }
+                    accepted
+                } else {
+                    vec![arg.lua_type(types)?]
+                };
+                if arg.has_default() || arg.is_nullable(types) {
+                    accepted.insert(0, "nil".to_string());
+                }
+
+                Some(accepted)
+            }
+            None if position == args.len() && loose_vec2.is_some() => {
+                Some(vec!["nil".to_string(), "number".to_string()])
+            }
+            None if func.format_arg().is_some() => None,
+            None => Some(vec!["nil".to_string()]),
+        }
+    }
+
+    /// Whether every call the later function accepts is already taken by the earlier function.
+    fn shadows(earlier: &Function, later: &Function, types: &[Type]) -> bool {
+        let (earlier_min, earlier_max) = Self::arity(earlier, types);
+        let (later_min, later_max) = Self::arity(later, types);
+        let covers_arity = earlier_min <= later_min
+            && match (earlier_max, later_max) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(earlier_max), Some(later_max)) => later_max <= earlier_max,
+            };
+
+        let positions = earlier.lua_args().len().max(later.lua_args().len()) + 1;
+        covers_arity
+            && (0..positions)
+                .take_while(|position| later_max.is_none_or(|max| *position < max))
+                .all(|position| {
+                    match (
+                        Self::accepted_types(earlier, position, types),
+                        Self::accepted_types(later, position, types),
+                    ) {
+                        (None, _) => true,
+                        (Some(_), None) => false,
+                        (Some(earlier), Some(later)) => {
+                            later.iter().all(|lua_type| earlier.contains(lua_type))
+                        }
+                    }
+                })
+    }
+
+    /// The Lua condition that's true when the arguments `a1..aN` match the function.
+    fn condition(func: &Function, types: &[Type]) -> String {
+        let args = func.lua_args();
+        let loose_vec2 = func.loose_vec2(types);
+
+        let count = match Self::arity(func, types) {
+            (required, None) => format!("n >= {}", required),
+            (required, Some(max)) if required == max => format!("n == {}", required),
+            (required, Some(max)) => format!("n >= {} and n <= {}", required, max),
+        };
+
+        std::iter::once(count)
+            .chain(args.iter().enumerate().filter_map(|(position, arg)| {
+                let index = position + 1;
+                let is_loose_vec2 = loose_vec2.is_some_and(|loose| std::ptr::eq(loose, *arg));
+
+                let mut checks = Self::accepted_types(func, position, types)?
+                    .into_iter()
+                    .map(|lua_type| match lua_type.as_str() {
+                        "nil" => format!("a{} == nil", index),
+                        // The loose `y` must be passed as well
+                        "number" if is_loose_vec2 => format!(
+                            "type(a{}) == \"number\" and type(a{}) == \"number\"",
+                            index,
+                            index + 1
+                        ),
+                        lua_type => format!("type(a{}) == \"{}\"", index, lua_type),
+                    })
+                    .collect::<Vec<_>>();
+
+                Some(if checks.len() == 1 {
+                    checks.remove(0)
+                } else {
+                    format!("({})", checks.join(" or "))
+                })
+            }))
+            .join(" and ")
+    }
+
+    /// The documentation of all overloads, the same comment is only included once.
+    pub fn comment(&self) -> Option<String> {
+        let comments = self
+            .functions
+            .iter()
+            .filter_map(|func| func.comment())
+            .unique()
+            .collect::<Vec<_>>();
+
+        if comments.is_empty() {
+            None
+        } else {
+            Some(comments.join("\n"))
+        }
+    }
+
+    /// The Markdown reference entries of all overloads.
+    pub fn markdown(&self, path: &str, types: &[Type], imgui_url: &str) -> String {
+        self.functions
+            .iter()
+            .map(|func| {
+                let entry = func.markdown(path, types, imgui_url);
+                match self.split_from() {
+                    Some(name) => format!(
+                        "{}\nAn overload of `{}` with a name of its own, its arguments can't be told \
+                         apart from the other overloads.\n",
+                        entry,
+                        name.imgui()
+                    ),
+                    None => entry,
+                }
+            })
+            .join("\n")
+    }
+
+    /// The LuaLS function type, the union of all overloads.
+    pub fn doc_signature(&self, types: &[Type]) -> String {
+        if self.functions.len() == 1 {
+            return self.functions[0].doc_signature(types);
+        }
+
+        // Without the parentheses the union would be part of the return type
+        self.functions
+            .iter()
+            .map(|func| format!("({})", func.doc_signature(types)))
+            .join("|")
+    }
+
+    /// The Lua function assigned to the path, dispatching to the right overload.
+    pub fn lua_function(&self, path: &str, types: &[Type]) -> String {
+        if self.functions.len() == 1 {
+            return self.functions[0].lua_function(path, types);
+        }
+
+        let functions = &self.functions;
+        let max_args = functions
+            .iter()
+            .map(|func| {
+                Self::arity(func, types)
+                    .1
+                    .unwrap_or_else(|| func.lua_args().len())
+            })
+            .max()
+            .unwrap_or(0);
+
+        // The dispatcher comes first so the annotations of the overloads belong to it, the
+        // implementations are only looked up when it's called
+        format!(
+            indoc!(
+                r##"
+            function {path}(...)
+                local n = select("#", ...)
+            {locals}{dispatch}
+                overload_error("{path}", ...)
+            end
+
+            {overloads}"##
+            ),
+            overloads = functions
+                .iter()
+                .map(|func| func
+                    .lua_function(&format!("overloads.{}", func.cimgui_name().imgui()), types))
+                .join("\n"),
+            path = path,
+            locals = if max_args > 0 {
+                format!(
+                    "    local {} = ...\n",
+                    (1..=max_args).map(|index| format!("a{}", index)).join(", ")
+                )
+            } else {
+                String::new()
+            },
+            dispatch = functions
+                .iter()
+                .map(|func| format!(
+                    "    if {} then\n        return overloads.{}(...)\n    end",
+                    Self::condition(func, types),
+                    func.cimgui_name().imgui()
+                ))
+                .join("\n"),
+        )
+    }
+}
+
+impl Render for Overloads {
+    fn lua(&self, types: &[Type]) -> String {
+        // TODO: Make this configurable
+        self.lua_function(&format!("{}.{}", "gui", self.name.lua()), types)
+    }
+
+    /// Get the LuaLS annotations, overloaded functions list all signatures.
+    fn doc(&self, types: &[Type]) -> String {
+        if self.functions.len() == 1 {
+            return self.functions[0].doc(types);
+        }
+
+        std::iter::once(comment::prefixed(self.comment().as_deref(), "---"))
+            .chain(
+                self.functions
+                    .iter()
+                    .map(|func| format!("---@overload {}\n", func.doc_signature(types))),
+            )
+            .collect()
+    }
+
+    /// Get the Teal declarations, a field for every overload makes it a polymorphic function.
+    fn teal(&self, types: &[Type]) -> String {
+        self.functions
+            .iter()
+            .map(|func| {
+                format!(
+                    "{}{}: {}\n",
+                    comment::prefixed(func.comment(), "--"),
+                    self.name.lua(),
+                    func.teal_signature(types)
+                )
+            })
+            .collect()
+    }
+
+    /// Get the cdef definition of all overloads.
+    fn cdef(&self, types: &[Type]) -> String {
+        self.functions
+            .iter()
+            .map(|func| func.cdef(types))
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        c_type::CType,
+        function::{Arg, Function},
+        render::Render,
+    };
+
+    #[test]
+    fn dispatch() -> anyhow::Result<()> {
+        let str_id = Function::from_parsed(
+            "PushID".into(),
+            "igPushIDStr".into(),
+            vec![Arg::from_parsed(
+                "str_id".to_string(),
+                None,
+                CType::parse("char*")?,
+                1,
+            )],
+            None,
+            None,
+        );
+        let int_id = Function::from_parsed(
+            "PushID".into(),
+            "igPushIDInt".into(),
+            vec![Arg::from_parsed(
+                "int_id".to_string(),
+                None,
+                CType::parse("int")?,
+                1,
+            )],
+            None,
+            None,
+        );
+
+        let overloads = super::Overloads::group(vec![str_id, int_id], &[]);
+        assert_eq!(overloads.len(), 1);
+
+        assert_eq!(
+            overloads[0].lua(&[]),
+            indoc::indoc!(
+                r##"
+                function gui.push_id(...)
+                    local n = select("#", ...)
+                    local a1 = ...
+                    if n == 1 and type(a1) == "number" then
+                        return overloads.igPushIDInt(...)
+                    end
+                    if n == 1 and type(a1) == "string" then
+                        return overloads.igPushIDStr(...)
+                    end
+                    overload_error("gui.push_id", ...)
+                end
+
+                function overloads.igPushIDInt(int_id)
+                    int_id = args_check(int_id, "number", 1, "int_id")
+                    C.igPushIDInt(int_id)
+                end
+
+                function overloads.igPushIDStr(str_id)
+                    str_id = args_check(str_id, "string", 1, "str_id")
+                    C.igPushIDStr(str_id)
+                end
+                "##
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn dispatch_nullable() -> anyhow::Result<()> {
+        let ptr_id = Function::from_parsed(
+            "PushID".into(),
+            "igPushIDPtr".into(),
+            vec![Arg::from_parsed(
+                "ptr_id".to_string(),
+                None,
+                CType::parse("const void*")?,
+                1,
+            )],
+            None,
+            None,
+        );
+        let int_id = Function::from_parsed(
+            "PushID".into(),
+            "igPushIDInt".into(),
+            vec![Arg::from_parsed(
+                "int_id".to_string(),
+                None,
+                CType::parse("int")?,
+                1,
+            )],
+            None,
+            None,
+        );
+
+        // NULL can be passed explicitly, but the pointer can't be left out without a default
+        let overloads = super::Overloads::group(vec![ptr_id, int_id], &[]);
+        assert!(overloads[0].lua(&[]).contains(
+            "    if n == 1 and (a1 == nil or type(a1) == \"cdata\") then\n\
+             \x20       return overloads.igPushIDPtr(...)\n"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn dispatch_vec() -> anyhow::Result<()> {
+        use crate::{r#struct::Struct, r#type::Type};
+
+        let types = [Type::Struct(Struct::from_parsed("ImVec2".into(), vec![]))];
+        let float = Function::from_parsed(
+            "PushStyleVar".into(),
+            "igPushStyleVarFloat".into(),
+            vec![
+                Arg::from_parsed("idx".to_string(), None, CType::parse("int")?, 1),
+                Arg::from_parsed("val".to_string(), None, CType::parse("float")?, 2),
+            ],
+            None,
+            None,
+        );
+        let vec2 = Function::from_parsed(
+            "PushStyleVar".into(),
+            "igPushStyleVarVec2".into(),
+            vec![
+                Arg::from_parsed("idx".to_string(), None, CType::parse("int")?, 1),
+                Arg::from_parsed("val".to_string(), None, CType::parse("const ImVec2")?, 2),
+            ],
+            None,
+            None,
+        );
+
+        let overloads = super::Overloads::group(vec![vec2, float], &types);
+        assert_eq!(overloads.len(), 1);
+
+        // Vectors can be cdata & the loose `y` is an extra argument
+        let lua = overloads[0].lua(&types);
+        assert!(lua.contains(
+            "    if n == 2 and type(a1) == \"number\" and type(a2) == \"number\" then\n"
+        ));
+        assert!(lua.contains(
+            "    if n >= 2 and n <= 3 and type(a1) == \"number\" and (type(a2) == \"table\" \
+             or type(a2) == \"cdata\" or type(a2) == \"number\" and type(a3) == \"number\") then\n"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn unreachable() -> anyhow::Result<()> {
+        let menu_item = |cimgui_name: &str, selected: &str| -> anyhow::Result<Function> {
+            Ok(Function::from_parsed(
+                "MenuItem".into(),
+                cimgui_name.into(),
+                vec![
+                    Arg::from_parsed("label".to_string(), None, CType::parse("const char*")?, 1),
+                    Arg::from_parsed(
+                        "selected".to_string(),
+                        Some("false".to_string()),
+                        CType::parse(selected)?,
+                        2,
+                    ),
+                ],
+                None,
+                Some(CType::parse("bool")?),
+            ))
+        };
+
+        // Both accept a string & an optional boolean, so the second one gets its own name
+        let overloads = super::Overloads::group(
+            vec![
+                menu_item("igMenuItemBoolPtr", "bool*")?,
+                menu_item("igMenuItemBool", "bool")?,
+            ],
+            &[],
+        );
+        assert_eq!(
+            overloads
+                .iter()
+                .map(|overloads| overloads.name().lua())
+                .collect::<Vec<_>>(),
+            vec!["menu_item", "menu_item_bool_ptr"]
+        );
+        assert_eq!(
+            overloads[1].functions()[0].cimgui_name().imgui(),
+            "igMenuItemBoolPtr"
+        );
+
+        // The reference mentions the rename
+        assert!(overloads[0].split_from().is_none());
+        assert!(overloads[1]
+            .markdown("gui.menu_item_bool_ptr", &[], "https://example.com")
+            .contains("An overload of `MenuItem` with a name of its own"));
+
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
 use crate::{
-    function::{Arg, Function},
-    json,
+    c_type::CType,
+    comment, default,
+    function::{Arg, Function, Kind},
+    json, markdown,
+    overload::Overloads,
     r#enum::{Enum, Value},
     r#struct::{Field, Struct},
-    r#type::Type,
+    r#type::{Type, TypeList},
     render::Render,
+    scope::SCOPES,
+    typedef::Typedef,
 };
 use anyhow::{anyhow, Result};
 use indoc::indoc;
@@ -19,6 +24,8 @@ pub struct Parser {
     structs: HashMap<String, json::Struct>,
     enums: HashMap<String, json::Enum>,
     locations: HashMap<String, json::Location>,
+    /// The lines of the ImGui headers, by the filename used in the locations.
+    headers: HashMap<String, Vec<String>>,
 }
 
 impl Parser {
@@ -64,31 +71,58 @@ impl Parser {
         Ok(())
     }
 
+    /// Add an ImGui header to read the comments of the declarations from, the filename is
+    /// without the extension, such as `imgui` for `imgui.h`.
+    pub fn add_header(&mut self, filename: &str, source: &str) {
+        self.headers.insert(
+            filename.to_string(),
+            source.lines().map(str::to_string).collect(),
+        );
+    }
+
+    /// The comments documenting the declaration at the location in one of the headers.
+    fn comment(&self, filename: &str, line_number: i64) -> Option<String> {
+        self.headers
+            .get(filename)
+            .and_then(|lines| comment::extract(lines, line_number))
+    }
+
+    /// The title of the section of the header the declaration at the location is part of.
+    fn section(&self, filename: &str, line_number: i64) -> Option<String> {
+        self.headers
+            .get(filename)
+            .and_then(|lines| comment::section(lines, line_number))
+    }
+
     /// Convert everything to usable data.
     pub fn parse(&self) -> Result<Data> {
-        let mut types = Type::default_list();
+        let mut types = self
+            .structs
+            .iter()
+            .map(|(name, r#struct)| {
+                let fields = r#struct
+                    .0
+                    .iter()
+                    .map(|field| {
+                        // The array dimensions are part of the field name in cimgui
+                        let mut parts = field.name.split('[');
+                        let name = parts.next().unwrap_or_default();
+                        let dimensions = parts
+                            .map(|dimension| Some(dimension.trim_end_matches(']').to_string()))
+                            .collect();
 
-        types.append(
-            &mut self
-                .structs
-                .iter()
-                .map(|(name, r#struct)| {
-                    let fields = r#struct
-                        .0
-                        .iter()
-                        .map(|field| {
-                            Field::from_parsed(
-                                (&field.name).into(),
-                                field.template_type.clone(),
-                                field.r#type.clone(),
-                            )
-                        })
-                        .collect();
+                        Ok(Field::from_parsed(
+                            name.into(),
+                            field.bitfield.clone(),
+                            field.template_type.clone(),
+                            CType::parse(&field.r#type)?.with_dimensions(dimensions),
+                        ))
+                    })
+                    .collect::<Result<_>>()?;
 
-                    Type::Struct(Struct::from_parsed(name.into(), fields))
-                })
-                .collect::<Vec<_>>(),
-        );
+                Ok(Type::Struct(Struct::from_parsed(name.into(), fields)))
+            })
+            .collect::<Result<Vec<_>>>()?;
         types.append(
             &mut self
                 .enums
@@ -98,11 +132,7 @@ impl Parser {
                         .0
                         .iter()
                         .map(|value| {
-                            Value::from_parsed(
-                                value.name.clone(),
-                                value.value.clone(),
-                                value.calc_value.unwrap_or(0),
-                            )
+                            Value::from_parsed(value.name.clone(), value.calc_value.unwrap_or(0))
                         })
                         .collect();
 
@@ -111,16 +141,69 @@ impl Parser {
                 .collect::<Vec<_>>(),
         );
 
+        // Add the structs cimgui generates for the templated ImVector<T> fields
+        let templates = types
+            .iter()
+            .filter_map(|r#type| match r#type {
+                Type::Struct(r#struct) => Some(r#struct.fields().iter()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|field| field.template())
+            .unique_by(|(name, _)| name.to_string())
+            .filter(|(name, _)| types.find(name).is_err())
+            .map(|(name, template_type)| {
+                Ok(Type::Struct(Struct::from_template(
+                    name.into(),
+                    template_type,
+                )?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        types.extend(templates);
+
+        // Add the typedefs, the ones aliasing a struct with the same name are already declared
+        let typedefs = self
+            .typedefs
+            .iter()
+            .flat_map(|typedefs| typedefs.0.iter())
+            .filter(|(name, _)| types.find(name).is_err())
+            .unique_by(|(name, _)| name.to_string())
+            .map(|(name, r#type)| {
+                Ok(Type::Typedef(Typedef::from_parsed(
+                    name.into(),
+                    CType::parse(r#type)?,
+                )))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        types.extend(typedefs);
+
         // Add the location to each type when applicable
         self.locations.iter().for_each(|(name, location)| {
             types.iter_mut().for_each(|r#type| {
                 if r#type.is_same(name) {
-                    r#type.add_location(&location.filename(), location.line_number());
+                    r#type.add_location(location.filename(), location.line_number());
+                    if let Some(comment) = self.comment(location.filename(), location.line_number())
+                    {
+                        r#type.add_comment(comment);
+                    }
                 }
             });
         });
 
-        // Get an iterator of all methods & functions
+        // The destructors of the structs, attached to the constructed objects
+        let destructors = self
+            .defs
+            .iter()
+            .flat_map(|defs| defs.0.values())
+            .flatten()
+            .filter(|def| def.destructor)
+            .map(|def| (def.struct_name.as_str(), def.ov_cimgui_name.as_str()))
+            .collect::<HashMap<_, _>>();
+
+        // Get all methods & functions
+        let mut skipped = vec![];
+        let type_list: &[Type] = &types;
+        let destructors = &destructors;
         let (methods, functions): (Vec<_>, Vec<_>) = self
             .defs
             .iter()
@@ -129,58 +212,140 @@ impl Parser {
                 defs.iter()
                     // Only parse non-templated functions
                     .filter(|def| !def.templated)
+                    // Prefer the nonUDT variants, they return structs through a pointer
+                    .filter(move |def| !defs.iter().any(|other| other.replaces(def)))
                     .map(move |def| {
-                        let args = def
-                            .args_t
+                        // The struct pointer of nonUDT variants is the first argument
+                        let (out_ret, args_t) = match (def.non_udt, def.args_t.split_first()) {
+                            (Some(_), Some((out_ret, args_t))) => (
+                                CType::parse(&out_ret.r#type)?.pointee(),
+                                args_t,
+                            ),
+                            _ => (None, def.args_t.as_slice()),
+                        };
+
+                        // The function is skipped when a default value can't be translated
+                        let mut invalid_default = None;
+                        let args = args_t
                             .iter()
                             .enumerate()
                             .map(|(index, arg)| {
-                                Arg::from_parsed(
+                                // Default values are C++ expressions that need to become Lua
+                                let default_value = match def
+                                    .defaults
+                                    .get(&arg.name)
+                                    .map(|default_value| default::lua_expression(default_value, type_list))
+                                    .transpose()
+                                {
+                                    Ok(default_value) => default_value,
+                                    Err(err) => {
+                                        invalid_default.get_or_insert(format!(
+                                            "default value of argument \"{}\" can't be translated to Lua: {}",
+                                            arg.name, err
+                                        ));
+                                        None
+                                    }
+                                };
+
+                                Ok(Arg::from_parsed(
                                     arg.name.clone(),
-                                    def.defaults.get(&arg.name).map(|s| s.clone()),
-                                    arg.r#type.clone(),
+                                    default_value,
+                                    CType::parse(&arg.r#type)?,
                                     index as u8 + 1,
-                                )
+                                ))
                             })
-                            .collect();
+                            .collect::<Result<_>>()?;
 
-                        (
+                        Ok((
                             // Convert the string to an option
                             match def.struct_name.as_str() {
                                 "" => None,
-                                value => Some(value.clone()),
+                                value => Some(value),
                             },
                             Function::from_parsed(
                                 // Use the func name and if that's missing the cimgui name
                                 def.func_name.as_ref().unwrap_or(&name.to_string()).into(),
+                                // The overloaded name is the actual symbol to call
+                                (&def.ov_cimgui_name).into(),
                                 args,
                                 // Parse the location
                                 def.location
                                     .as_ref()
                                     .map(|loc| (loc.filename().to_string(), loc.line_number())),
-                                def.ret.clone(),
-                                def.signature.clone(),
-                            ),
-                        )
+                                // Constructors return a pointer to the new object
+                                match &def.ret {
+                                    Some(ret) => Some(CType::parse(ret)?),
+                                    None if def.constructor => {
+                                        Some(CType::parse(&format!("{}*", def.struct_name))?)
+                                    }
+                                    None => None,
+                                },
+                            )
+                            .with_comment(def.location.as_ref().and_then(|loc| {
+                                self.comment(loc.filename(), loc.line_number())
+                            }))
+                            .with_section(def.location.as_ref().and_then(|loc| {
+                                self.section(loc.filename(), loc.line_number())
+                            }))
+                            .with_out_ret(out_ret)
+                            .with_kind(if def.constructor {
+                                Kind::Constructor {
+                                    destructor: destructors
+                                        .get(def.struct_name.as_str())
+                                        .map(|destructor| (*destructor).into()),
+                                }
+                            } else if def.destructor {
+                                Kind::Destructor
+                            } else {
+                                Kind::Function
+                            }),
+                            invalid_default,
+                        ))
                     })
             })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            // Leave out the functions that can't be called from LuaJIT
+            .filter_map(|(struct_name, func, invalid_default)| {
+                match invalid_default.or_else(|| func.unbindable_reason(type_list)) {
+                    Some(reason) => {
+                        skipped.push((func.cimgui_name().imgui().to_string(), reason));
+                        None
+                    }
+                    None => Some((struct_name, func)),
+                }
+            })
             // Split into functions and methods
             .partition(|(struct_name, _)| struct_name.is_some());
+        skipped.sort();
 
-        // Add the methods to the structs
-        for (struct_name, method) in methods.into_iter() {
+        // Add the methods to the structs, grouping the overloaded ones
+        let methods = methods
+            .into_iter()
             // Safe to unwrap because all instances that don't have a value are already partitioned
-            let struct_name = struct_name.unwrap();
+            .map(|(struct_name, method)| (struct_name.unwrap(), method))
+            .into_group_map()
+            .into_iter()
+            .map(|(struct_name, methods)| (struct_name, Overloads::group(methods, &types)))
+            .collect::<Vec<_>>();
+        for (struct_name, methods) in methods.into_iter() {
             match types.iter_mut().find(|r#type| r#type.is_same(struct_name)) {
-                Some(r#type) => r#type.add_method(method)?,
+                Some(r#type) => r#type.add_methods(methods)?,
                 None => return Err(anyhow!("No struct \"{}\" for method found", struct_name)),
             }
         }
 
-        // Extract just the functions
-        let functions = functions.into_iter().map(|(_, func)| func).collect();
+        // Extract just the functions and group the overloaded ones
+        let functions = Overloads::group(
+            functions.into_iter().map(|(_, func)| func).collect(),
+            &types,
+        );
 
-        Ok(Data { functions, types })
+        Ok(Data {
+            functions,
+            types,
+            skipped,
+        })
     }
 }
 
@@ -188,7 +353,9 @@ impl Parser {
 #[derive(Debug, Default)]
 pub struct Data {
     types: Vec<Type>,
-    functions: Vec<Function>,
+    functions: Vec<Overloads>,
+    /// The cimgui symbols of the functions that can't be bound, with the reason why.
+    skipped: Vec<(String, String)>,
 }
 
 impl Data {
@@ -196,41 +363,427 @@ impl Data {
     pub fn lua(&self) -> String {
         format!(
             indoc! {r#"
+            local ffi = require("ffi")
+
+            -- Load the C definitions from the "cdefs" module next to this one
+            ffi.cdef(require(... and (...):gsub("[^.]+$", "cdefs") or "cdefs"))
+
+            -- Use the cimgui shared library when available, otherwise assume it's linked statically
+            local C = ffi.C
+            local has_lib, lib = pcall(ffi.load, "cimgui")
+            if has_lib then
+                C = lib
+            end
+
             local gui = {{}}
             gui.__index = gui
 
+            -- Return ImVec2 & ImVec4 values as {{x = ..., y = ...}} tables instead of cdata
+            gui.vec_as_table = false
+
+            -- All overloaded variants of functions, by their cimgui name
+            local overloads = {{}}
+            gui.overloads = overloads
+
             {args_lua}
+            {array_lua}
+            {vec_lua}
+            {flags_lua}
+            {callback_lua}
+            {text_lua}
+            {scope_lua}
+            {overload_lua}
+            --[[ Enums ]]
+
+            -- Reverse lookup of the enums, from values to names
+            gui.names = {{}}
+
+            {enums}
+            --[[ Structs ]]
+
+            {structs}
             --[[ Functions ]]
 
             {functions}
+            --[[ Scopes ]]
+
+            -- Helpers calling a function between Begin/End & Push/Pop pairs
+            {scopes}
             return gui
         "#},
             args_lua = include_str!("lua/args.lua"),
+            array_lua = include_str!("lua/array.lua"),
+            vec_lua = include_str!("lua/vec.lua"),
+            flags_lua = include_str!("lua/flags.lua"),
+            callback_lua = include_str!("lua/callback.lua"),
+            text_lua = include_str!("lua/text.lua"),
+            scope_lua = include_str!("lua/scope.lua"),
+            overload_lua = include_str!("lua/overload.lua"),
+            enums = self
+                .types
+                .iter()
+                .filter(|r#type| matches!(r#type, Type::Enum(_)))
+                .sorted_by_key(|r#type| r#type.name())
+                .map(|r#type| format!("{}{}", r#type.doc(&self.types), r#type.lua(&self.types)))
+                .join("\n"),
+            structs = self
+                .types
+                .iter()
+                .filter(|r#type| matches!(r#type, Type::Struct(_)))
+                .sorted_by_key(|r#type| r#type.name())
+                .map(|r#type| format!("{}{}", r#type.doc(&self.types), r#type.lua(&self.types)))
+                .join("\n"),
             functions = self
                 .functions
                 .iter()
-                .map(|func| func.lua(&self.types))
-                .intersperse("\n".to_string())
+                .map(|func| format!("{}{}", func.doc(&self.types), func.lua(&self.types)))
+                .join("\n"),
+            scopes = SCOPES
+                .iter()
+                .filter_map(|scope| scope.lua(&self.functions))
+                .join("\n")
+        )
+    }
+
+    /// Render the Teal declarations of the Lua module.
+    pub fn teal(&self) -> String {
+        // Everything is declared inside the record of the module
+        let indent = |teal: String| {
+            teal.lines()
+                .map(|line| format!("    {}\n", line))
+                .collect::<String>()
+        };
+
+        format!(
+            indoc! {r#"
+            local record gui
+                -- Return ImVec2 & ImVec4 values as tables instead of cdata
+                vec_as_table: boolean
+
+                -- Reverse lookup of the enums, from values to names
+                names: {{string:{{integer:string}}}}
+
+                -- All overloaded variants of functions, by their cimgui name
+                overloads: {{string:function}}
+
+                flags: function(enum: any, ...: integer | string): integer
+                flag_names: function(enum: any, value: integer): {{string}}
+
+            {enums}
+            {structs}
+            {functions}
+            {scopes}end
+
+            return gui
+        "#},
+            enums = self
+                .types
+                .iter()
+                .filter(|r#type| matches!(r#type, Type::Enum(_)))
+                .sorted_by_key(|r#type| r#type.name())
+                .map(|r#type| indent(r#type.teal(&self.types)))
+                .join("\n"),
+            structs = self
+                .types
+                .iter()
+                .filter(|r#type| matches!(r#type, Type::Struct(_)))
+                .sorted_by_key(|r#type| r#type.name())
+                .map(|r#type| indent(r#type.teal(&self.types)))
+                .join("\n"),
+            functions = self
+                .functions
+                .iter()
+                .map(|func| indent(func.teal(&self.types)))
+                .collect::<String>(),
+            scopes = SCOPES
+                .iter()
+                .filter_map(|scope| scope.teal(&self.functions, &self.types))
+                .map(indent)
                 .collect::<String>()
         )
     }
 
+    /// Render the Markdown reference, the file names with the content of their pages.
+    pub fn markdown(&self, imgui_url: &str) -> Vec<(String, String)> {
+        let structs = self
+            .types
+            .iter()
+            .filter_map(|r#type| match r#type {
+                Type::Struct(r#struct) => Some(r#struct),
+                _ => None,
+            })
+            .sorted_by_key(|r#struct| r#struct.name().lua_type())
+            .collect::<Vec<_>>();
+
+        // The functions are split in pages by the section of the header they're declared in
+        let sections = self
+            .functions
+            .iter()
+            .map(|overloads| {
+                (
+                    (
+                        overloads.location().map(|(filename, _)| filename.as_str()),
+                        overloads.section(),
+                    ),
+                    overloads,
+                )
+            })
+            .into_group_map()
+            .into_iter()
+            .map(|(key, functions)| {
+                let functions = functions
+                    .into_iter()
+                    .sorted_by_key(|overloads| overloads.location().map(|(_, line)| *line))
+                    .collect::<Vec<_>>();
+                (key, functions)
+            })
+            .sorted_by_key(|((header, _), functions)| {
+                (*header, functions[0].location().map(|(_, line)| *line))
+            })
+            .map(|((header, section), functions)| {
+                let title = match (header, section) {
+                    (_, Some(section)) => section.to_string(),
+                    (Some(header), None) => markdown::header_path(header),
+                    (None, None) => "Other functions".to_string(),
+                };
+                let page = format!(
+                    "# {}\n\n{}{}",
+                    title,
+                    header.map_or(String::new(), |header| format!(
+                        "Declared in {}.\n\n",
+                        markdown::header_link(header, imgui_url)
+                    )),
+                    functions
+                        .iter()
+                        .map(|func| func.markdown(
+                            &format!("gui.{}", func.name().lua()),
+                            &self.types,
+                            imgui_url
+                        ))
+                        .join("\n")
+                );
+
+                (
+                    markdown::page_filename(header, section),
+                    title,
+                    header,
+                    page,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // The overloads the dispatchers can't reach, bound to the cimgui names instead
+        let renamed = self
+            .functions
+            .iter()
+            .map(|overloads| {
+                let page = markdown::page_filename(
+                    overloads.location().map(|(filename, _)| filename.as_str()),
+                    overloads.section(),
+                );
+                (format!("gui.{}", overloads.name().lua()), page, overloads)
+            })
+            .chain(structs.iter().flat_map(|r#struct| {
+                let lua_type = r#struct.name().lua_type();
+                r#struct.bound_methods().map(move |(key, overloads)| {
+                    (
+                        format!("gui.{}.{}", lua_type, key),
+                        format!("{}.md", lua_type),
+                        overloads,
+                    )
+                })
+            }))
+            .filter_map(|(path, page, overloads)| {
+                overloads.split_from().map(|name| {
+                    vec![
+                        format!("[{}]({})", markdown::code(&path), page),
+                        markdown::code(name.imgui()),
+                        markdown::code(overloads.functions()[0].cimgui_name().imgui()),
+                    ]
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let index = format!(
+            indoc! {r#"
+            # ImGui Lua reference
+
+            - [Enums](enums.md)
+            - [Scoped helpers](scopes.md)
+
+            ## Functions
+
+            {functions}
+            ## Renamed overloads
+
+            Overloads with arguments the others can't be told apart from at runtime are bound to
+            a name of their own.
+
+            {renamed}
+            ## Structs
+
+            {structs}
+        "#},
+            functions = sections
+                .iter()
+                .group_by(|(_, _, header, _)| *header)
+                .into_iter()
+                .map(|(header, pages)| format!(
+                    "### {}\n\n{}",
+                    header.map_or("Other".to_string(), markdown::header_path),
+                    pages
+                        .map(|(filename, title, _, _)| format!("- [{}]({})\n", title, filename))
+                        .collect::<String>()
+                ))
+                .join("\n"),
+            renamed = markdown::table(&["Lua", "Overload of", "Calls"], &renamed),
+            structs = structs
+                .iter()
+                .map(|r#struct| format!(
+                    "- [{name}]({name}.md)\n",
+                    name = r#struct.name().lua_type()
+                ))
+                .collect::<String>()
+        );
+
+        let scopes = format!(
+            indoc! {r#"
+            # Scoped helpers
+
+            Call the function passed as the last argument between the begin & end functions, the
+            other arguments are passed to the begin function. The end function is also called when
+            the function errors.
+
+            {scopes}"#},
+            scopes = markdown::table(
+                &["Helper", "Begin", "End", "End is called"],
+                &SCOPES
+                    .iter()
+                    .filter_map(|scope| scope.markdown(&self.functions))
+                    .collect::<Vec<_>>()
+            )
+        );
+
+        let enums = format!(
+            "# Enums\n\n{}",
+            self.types
+                .iter()
+                .filter_map(|r#type| match r#type {
+                    Type::Enum(r#enum) => Some(r#enum),
+                    _ => None,
+                })
+                .sorted_by_key(|r#enum| r#enum.lua_name())
+                .map(|r#enum| r#enum.markdown(imgui_url))
+                .join("\n")
+        );
+
+        vec![
+            ("README.md".to_string(), index),
+            ("scopes.md".to_string(), scopes),
+            ("enums.md".to_string(), enums),
+        ]
+        .into_iter()
+        .chain(
+            sections
+                .into_iter()
+                .map(|(filename, _, _, page)| (filename, page)),
+        )
+        .chain(structs.iter().map(|r#struct| {
+            (
+                format!("{}.md", r#struct.name().lua_type()),
+                r#struct.markdown(&self.types, imgui_url),
+            )
+        }))
+        .collect()
+    }
+
+    /// Render the functions that are left out of the bindings, with the reason why.
+    pub fn skipped_report(&self) -> String {
+        self.skipped
+            .iter()
+            .map(|(name, reason)| format!("{}: {}\n", name, reason))
+            .collect()
+    }
+
     /// Render the result as cdefs.
     pub fn cdefs(&self) -> String {
+        let (typedefs, types): (Vec<_>, Vec<_>) = self
+            .types
+            .declaration_order()
+            .into_iter()
+            .partition(|r#type| matches!(r#type, Type::Typedef(_)));
+
         format!(
             indoc! {r#"
             return [[
-            {cdefs}
+            {declarations}
+
+            {typedefs}
+
+            {types}
+
+            {functions}
             ]]
         "#},
-            cdefs = self
-                .functions
+            // Forward declare all structs so they can be referenced as pointers everywhere
+            declarations = types
+                .iter()
+                .filter(|r#type| matches!(r#type, Type::Struct(_)))
+                .map(|r#type| r#type.name())
+                .chain(self.opaque_types())
+                .map(|name| format!("typedef struct {name} {name};", name = name))
+                .join("\n"),
+            typedefs = typedefs
+                .iter()
+                .map(|r#type| r#type.cdef(&self.types))
+                .join("\n"),
+            types = types
                 .iter()
+                .map(|r#type| r#type.cdef(&self.types))
+                .join("\n\n"),
+            functions = self
+                .all_functions()
                 .map(|func| func.cdef(&self.types))
-                .intersperse("\n".to_string())
-                .collect::<String>()
+                .join("\n")
+        )
+    }
+
+    /// All functions, including the methods of structs.
+    fn all_functions(&self) -> impl Iterator<Item = &Overloads> {
+        self.functions.iter().chain(
+            self.types
+                .iter()
+                .filter_map(|r#type| match r#type {
+                    Type::Struct(r#struct) => Some(r#struct),
+                    _ => None,
+                })
+                .sorted_by_key(|r#struct| r#struct.name().imgui())
+                .flat_map(|r#struct| r#struct.methods()),
         )
     }
+
+    /// Types that are only used through pointers but never defined, such as `ImGuiContext`.
+    fn opaque_types(&self) -> impl Iterator<Item = &str> {
+        self.all_functions()
+            .flat_map(|overloads| overloads.functions())
+            .flat_map(|func| func.c_types())
+            .chain(
+                self.types
+                    .iter()
+                    .filter_map(|r#type| match r#type {
+                        Type::Struct(r#struct) => Some(r#struct.fields().iter()),
+                        _ => None,
+                    })
+                    .flatten()
+                    .map(|field| field.c_type()),
+            )
+            // Only pointers can point to incomplete types
+            .filter(|c_type| c_type.is_indirect())
+            .filter_map(|c_type| c_type.name())
+            .filter(|name| self.types.find(name).is_err())
+            .unique()
+            .sorted()
+    }
 }
 
 #[cfg(test)]
@@ -258,6 +811,77 @@ mod tests {
         // Parse everything
         let data = parser.parse()?;
 
+        // Functions taking a va_list can't be called
+        assert!(data.skipped_report().contains("igTextV: "));
+        assert!(!data.cdefs().contains("igTextV"));
+        assert!(!data.lua().contains("igTextV"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn markdown() -> anyhow::Result<()> {
+        let mut parser = super::Parser::new();
+        parser.add_json_typedefs(include_str!(
+            "../cimgui/generator/output/typedefs_dict.json"
+        ))?;
+        parser.add_json_structs_and_enums(include_str!(
+            "../cimgui/generator/output/structs_and_enums.json"
+        ))?;
+        parser.add_json_definitions(include_str!("../cimgui/generator/output/definitions.json"))?;
+        parser.add_json_definitions(include_str!(
+            "../cimgui/generator/output/impl_definitions.json"
+        ))?;
+        parser.add_header("imgui", include_str!("../cimgui/imgui/imgui.h"));
+
+        // Every section of the header gets its own page
+        let pages = parser.parse()?.markdown("https://example.com");
+        let page = |filename: &str| {
+            pages
+                .iter()
+                .find(|(page_filename, _)| page_filename == filename)
+                .map(|(_, page)| page.as_str())
+        };
+        assert!(page("README.md")
+            .is_some_and(|page| page.contains("- [Widgets: Main](imgui-widgets-main.md)\n")));
+        assert!(page("imgui-widgets-main.md").is_some_and(|page| {
+            page.starts_with("# Widgets: Main\n") && page.contains("`gui.button(")
+        }));
+        assert!(page("imgui_impl_opengl3.md").is_some_and(
+            |page| page.contains("(https://example.com/backends/imgui_impl_opengl3.h#L1)")
+        ));
+        assert_eq!(page("functions.md"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_default() -> anyhow::Result<()> {
+        let mut parser = super::Parser::new();
+        parser.add_json_definitions(
+            r#"{
+                "igSetColor": [{
+                    "args": "(int col)",
+                    "argsT": [{ "name": "col", "type": "int" }],
+                    "call_args": "(col)",
+                    "cimguiname": "igSetColor",
+                    "defaults": { "col": "IM_COL32(255,255,255,255)" },
+                    "funcname": "SetColor",
+                    "ov_cimguiname": "igSetColor",
+                    "ret": "void",
+                    "signature": "(int)",
+                    "stname": ""
+                }]
+            }"#,
+        )?;
+
+        // Default values that can't be translated skip the function instead of failing
+        let data = parser.parse()?;
+        assert!(data
+            .skipped_report()
+            .starts_with("igSetColor: default value of argument \"col\""));
+        assert!(!data.lua().contains("igSetColor"));
+
         Ok(())
     }
 }
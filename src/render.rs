@@ -3,9 +3,54 @@ use crate::r#type::Type;
 /// Implement this for structures that output Lua.
 pub trait Render {
     /// Output Lua.
-    fn lua(&self, types: &Vec<Type>) -> String;
-    /// Output Lua documentation.
-    fn doc(&self, types: &Vec<Type>) -> String;
+    fn lua(&self, types: &[Type]) -> String;
+    /// Output the LuaLS annotations of the Lua output.
+    fn doc(&self, types: &[Type]) -> String;
+    /// Output the Teal declarations of the Lua output.
+    fn teal(&self, types: &[Type]) -> String;
     /// Output LuaJIT cdef.
-    fn cdef(&self, types: &Vec<Type>) -> String;
+    fn cdef(&self, types: &[Type]) -> String;
+}
+
+/// The languages the types of the Lua output are described in.
+#[derive(Debug, Clone, Copy)]
+pub enum Annotation {
+    /// Comments for the Lua language server & EmmyLua.
+    LuaLs,
+    /// Teal declaration files.
+    Teal,
+}
+
+impl Annotation {
+    /// The type of values that are only known to be cdata.
+    pub fn cdata(self) -> &'static str {
+        match self {
+            Self::LuaLs => "ffi.cdata*",
+            Self::Teal => "any",
+        }
+    }
+
+    /// An array with elements of the type.
+    pub fn array(self, element: &str) -> String {
+        match self {
+            Self::LuaLs => format!("{}[]", element),
+            Self::Teal => format!("{{{}}}", element),
+        }
+    }
+
+    /// A value of the type that can also be `nil`, which every Teal type already allows.
+    pub fn nullable(self, r#type: &str) -> String {
+        match self {
+            Self::LuaLs => format!("{}|nil", r#type),
+            Self::Teal => r#type.to_string(),
+        }
+    }
+
+    /// A value that can be any of the types.
+    pub fn union(self, types: &[&str]) -> String {
+        match self {
+            Self::LuaLs => types.join("|"),
+            Self::Teal => types.join(" | "),
+        }
+    }
 }
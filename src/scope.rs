@@ -0,0 +1,322 @@
+use crate::{markdown, name::Name, overload::Overloads, r#type::Type};
+use indoc::indoc;
+use itertools::Itertools;
+
+/// When the function ending a scope must be called.
+#[derive(Debug, Clone, Copy)]
+pub enum End {
+    /// Always, whatever the begin function returned, such as `End` after `Begin`.
+    Always,
+    /// Only when the begin function returned `true`, such as `TreePop` after `TreeNode`.
+    Opened,
+    /// Always, the begin function doesn't return anything, such as `PopID` after `PushID`.
+    Void,
+}
+
+/// A pair of ImGui functions where the second one must always be called after the first.
+#[derive(Debug)]
+pub struct Scope {
+    /// The name of the Lua helper.
+    name: &'static str,
+    /// The ImGui name of the function starting the scope.
+    begin: &'static str,
+    /// The ImGui name of the function ending the scope.
+    end: &'static str,
+    /// Whether the end function is called whatever the begin function returned, otherwise that
+    /// depends on its return type.
+    always: bool,
+}
+
+/// All known Begin/End & Push/Pop pairs.
+pub const SCOPES: [Scope; 28] = [
+    Scope::always("window", "Begin", "End"),
+    Scope::always("child", "BeginChild", "EndChild"),
+    Scope::always("child_frame", "BeginChildFrame", "EndChildFrame"),
+    Scope::new("group", "BeginGroup", "EndGroup"),
+    Scope::new("tooltip", "BeginTooltip", "EndTooltip"),
+    Scope::new("menu_bar", "BeginMenuBar", "EndMenuBar"),
+    Scope::new("main_menu_bar", "BeginMainMenuBar", "EndMainMenuBar"),
+    Scope::new("menu", "BeginMenu", "EndMenu"),
+    Scope::new("popup", "BeginPopup", "EndPopup"),
+    Scope::new("popup_modal", "BeginPopupModal", "EndPopup"),
+    Scope::new("popup_context_item", "BeginPopupContextItem", "EndPopup"),
+    Scope::new(
+        "popup_context_window",
+        "BeginPopupContextWindow",
+        "EndPopup",
+    ),
+    Scope::new("popup_context_void", "BeginPopupContextVoid", "EndPopup"),
+    Scope::new("combo_popup", "BeginCombo", "EndCombo"),
+    Scope::new("tab_bar", "BeginTabBar", "EndTabBar"),
+    Scope::new("tab_item", "BeginTabItem", "EndTabItem"),
+    Scope::new(
+        "drag_drop_source",
+        "BeginDragDropSource",
+        "EndDragDropSource",
+    ),
+    Scope::new(
+        "drag_drop_target",
+        "BeginDragDropTarget",
+        "EndDragDropTarget",
+    ),
+    Scope::new("tree_node", "TreeNode", "TreePop"),
+    Scope::new("with_id", "PushID", "PopID"),
+    Scope::new("with_style_var", "PushStyleVar", "PopStyleVar"),
+    Scope::new("with_style_color", "PushStyleColor", "PopStyleColor"),
+    Scope::new("with_font", "PushFont", "PopFont"),
+    Scope::new("with_item_width", "PushItemWidth", "PopItemWidth"),
+    Scope::new("with_text_wrap_pos", "PushTextWrapPos", "PopTextWrapPos"),
+    Scope::new("with_clip_rect", "PushClipRect", "PopClipRect"),
+    Scope::new("with_button_repeat", "PushButtonRepeat", "PopButtonRepeat"),
+    Scope::new(
+        "with_allow_keyboard_focus",
+        "PushAllowKeyboardFocus",
+        "PopAllowKeyboardFocus",
+    ),
+];
+
+impl Scope {
+    /// A scope ended when the begin function returned `true`, or always when it returns nothing.
+    const fn new(name: &'static str, begin: &'static str, end: &'static str) -> Self {
+        Self {
+            name,
+            begin,
+            end,
+            always: false,
+        }
+    }
+
+    /// A scope that's always ended, whatever the begin function returned.
+    const fn always(name: &'static str, begin: &'static str, end: &'static str) -> Self {
+        Self {
+            always: true,
+            ..Self::new(name, begin, end)
+        }
+    }
+
+    /// The overloads of the begin function.
+    fn begin_overloads<'a>(&self, functions: &'a [Overloads]) -> Option<&'a Overloads> {
+        functions
+            .iter()
+            .find(|func| func.name().imgui() == self.begin)
+    }
+
+    /// When the end function must be called, from the return type of the begin function.
+    fn end_call(&self, functions: &[Overloads]) -> End {
+        let returns_bool = self.begin_overloads(functions).is_some_and(|overloads| {
+            overloads
+                .functions()
+                .iter()
+                .all(|func| func.ret().is_some_and(|ret| ret.is_bool()))
+        });
+
+        match (self.always, returns_bool) {
+            (true, _) => End::Always,
+            (false, true) => End::Opened,
+            (false, false) => End::Void,
+        }
+    }
+
+    /// Whether both the begin & end functions are bound.
+    fn is_bound(&self, functions: &[Overloads]) -> bool {
+        let is_bound = |name: &str| functions.iter().any(|func| func.name().imgui() == name);
+        is_bound(self.begin) && is_bound(self.end)
+    }
+
+    /// The Teal declarations of the helper, the arguments are those of the begin function.
+    pub fn teal(&self, functions: &[Overloads], types: &[Type]) -> Option<String> {
+        if !self.is_bound(functions) {
+            return None;
+        }
+
+        Some(
+            self.begin_overloads(functions)?
+                .functions()
+                .iter()
+                .flat_map(|func| func.teal_scoped_signatures(types))
+                .unique()
+                .map(|signature| format!("{}: {}\n", self.name, signature))
+                .collect(),
+        )
+    }
+
+    /// The Markdown reference table row with the functions called by the helper.
+    pub fn markdown(&self, functions: &[Overloads]) -> Option<Vec<String>> {
+        if !self.is_bound(functions) {
+            return None;
+        }
+
+        Some(vec![
+            markdown::code(&format!("gui.{}", self.name)),
+            markdown::code(&format!("gui.{}", Name::from(self.begin).lua())),
+            markdown::code(&format!("gui.{}", Name::from(self.end).lua())),
+            match self.end_call(functions) {
+                End::Always | End::Void => "Always",
+                End::Opened => "When the begin function returned `true`",
+            }
+            .to_string(),
+        ])
+    }
+
+    /// The Lua helper calling a function between the begin & end functions, `None` when either of
+    /// them isn't bound.
+    pub fn lua(&self, functions: &[Overloads]) -> Option<String> {
+        if !self.is_bound(functions) {
+            return None;
+        }
+
+        let begin = Name::from(self.begin).lua();
+        let end = Name::from(self.end).lua();
+        let end_call = match self.end_call(functions) {
+            End::Always => "always",
+            End::Opened => "opened",
+            End::Void => "void",
+        };
+
+        if begin == self.name {
+            // The helper replaces the plain function, which is still used without a function
+            Some(format!(
+                indoc! {r#"
+                do
+                    local {begin} = gui.{begin}
+                    function gui.{name}(...)
+                        if not is_scoped(...) then
+                            return {begin}(...)
+                        end
+                        return scoped("gui.{name}", {begin}, gui.{end}, "{end_call}", ...)
+                    end
+                end
+                "#},
+                name = self.name,
+                begin = begin,
+                end = end,
+                end_call = end_call
+            ))
+        } else {
+            Some(format!(
+                indoc! {r#"
+                function gui.{name}(...)
+                    return scoped("gui.{name}", gui.{begin}, gui.{end}, "{end_call}", ...)
+                end
+                "#},
+                name = self.name,
+                begin = begin,
+                end = end,
+                end_call = end_call
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn lua() -> anyhow::Result<()> {
+        use crate::{
+            c_type::CType,
+            function::{Arg, Function},
+            overload::Overloads,
+        };
+
+        let functions = [
+            ("Begin", "bool"),
+            ("End", "void"),
+            ("TreeNode", "bool"),
+            ("TreePop", "void"),
+            ("BeginTooltip", "bool"),
+            ("EndTooltip", "void"),
+        ]
+        .iter()
+        .map(|(name, ret)| -> anyhow::Result<Overloads> {
+            let args = match *name {
+                "Begin" => vec![
+                    Arg::from_parsed("name".to_string(), None, CType::parse("const char*")?, 1),
+                    Arg::from_parsed(
+                        "p_open".to_string(),
+                        Some("nil".to_string()),
+                        CType::parse("bool*")?,
+                        2,
+                    ),
+                ],
+                _ => vec![],
+            };
+
+            Ok(Overloads::group(
+                vec![Function::from_parsed(
+                    (*name).into(),
+                    format!("ig{}", name).into(),
+                    args,
+                    None,
+                    Some(CType::parse(ret)?),
+                )],
+                &[],
+            )
+            .remove(0))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+        assert_eq!(
+            super::SCOPES[0].lua(&functions),
+            Some(
+                indoc::indoc!(
+                    r#"
+                    function gui.window(...)
+                        return scoped("gui.window", gui.begin, gui.end_, "always", ...)
+                    end
+                    "#
+                )
+                .to_string()
+            )
+        );
+
+        let tree_node = super::SCOPES
+            .iter()
+            .find(|scope| scope.name == "tree_node")
+            .and_then(|scope| scope.lua(&functions));
+        assert_eq!(
+            tree_node,
+            Some(
+                indoc::indoc!(
+                    r#"
+                    do
+                        local tree_node = gui.tree_node
+                        function gui.tree_node(...)
+                            if not is_scoped(...) then
+                                return tree_node(...)
+                            end
+                            return scoped("gui.tree_node", tree_node, gui.tree_pop, "opened", ...)
+                        end
+                    end
+                    "#
+                )
+                .to_string()
+            )
+        );
+
+        // The tooltip is only ended when it was opened
+        let tooltip = super::SCOPES
+            .iter()
+            .find(|scope| scope.name == "tooltip")
+            .and_then(|scope| scope.lua(&functions));
+        assert!(tooltip.is_some_and(|tooltip| tooltip.contains("gui.end_tooltip, \"opened\"")));
+
+        // Unbound functions have no helper
+        assert_eq!(super::SCOPES[1].lua(&functions), None);
+
+        // The parameters of the begin function come before the body
+        assert_eq!(
+            super::SCOPES[0].teal(&functions, &[]),
+            Some(
+                indoc::indoc!(
+                    r#"
+                    window: function(name: string, body: function()): boolean, boolean
+                    window: function(name: string, p_open: boolean, body: function()): boolean, boolean
+                    "#
+                )
+                .to_string()
+            )
+        );
+
+        Ok(())
+    }
+}
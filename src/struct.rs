@@ -1,4 +1,16 @@
-use crate::{function::Function, name::Name};
+use crate::{
+    c_type::CType,
+    comment,
+    function::{self, Kind},
+    markdown,
+    name::{self, Name},
+    overload::Overloads,
+    r#type::Type,
+    render::{Annotation, Render},
+};
+use anyhow::Result;
+use indoc::indoc;
+use itertools::Itertools;
 
 /// Represents an ImGui structure.
 #[derive(Debug, Default)]
@@ -6,7 +18,8 @@ pub struct Struct {
     name: Name,
     fields: Vec<Field>,
     location: Option<(String, i64)>,
-    methods: Vec<Function>,
+    comment: Option<String>,
+    methods: Vec<Overloads>,
 }
 
 impl Struct {
@@ -19,37 +32,447 @@ impl Struct {
         }
     }
 
+    /// Create the struct cimgui generates for a templated `ImVector<T>`.
+    pub fn from_template(name: Name, template_type: &str) -> Result<Self> {
+        // Strip namespaces, cimgui doesn't use them in C
+        let template_type = template_type.rsplit("::").next().unwrap_or(template_type);
+
+        Ok(Self::from_parsed(
+            name,
+            vec![
+                Field::from_parsed("Size".into(), None, None, CType::parse("int")?),
+                Field::from_parsed("Capacity".into(), None, None, CType::parse("int")?),
+                Field::from_parsed(
+                    "Data".into(),
+                    None,
+                    None,
+                    CType::parse(&format!("{}*", template_type))?,
+                ),
+            ],
+        ))
+    }
+
     /// Add location information.
     pub fn add_location(&mut self, filename: &str, line_number: i64) {
         self.location = Some((filename.to_string(), line_number));
     }
 
-    /// Add a method.
-    pub fn add_method(&mut self, method: Function) {
-        self.methods.push(method);
+    /// Add the documentation from the comments in the ImGui header.
+    pub fn add_comment(&mut self, comment: String) {
+        self.comment = Some(comment);
+    }
+
+    /// Add methods, grouped by their Lua name.
+    pub fn add_methods(&mut self, methods: Vec<Overloads>) {
+        self.methods.extend(methods);
+    }
+
+    /// All methods, grouped by their Lua name.
+    pub fn methods(&self) -> &[Overloads] {
+        &self.methods
     }
 
     /// Check if this type is the same as the string.
     pub fn is_same(&self, r#type: &str) -> bool {
         self.name.imgui() == r#type
     }
+
+    /// The name of the struct.
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    /// All fields of the struct.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// The Markdown reference page with the fields & methods.
+    pub fn markdown(&self, types: &[Type], imgui_url: &str) -> String {
+        let mut page = format!(
+            "# {}\n\nWraps `{}`",
+            self.name.lua_type(),
+            self.name.imgui()
+        );
+        if let Some(location) = &self.location {
+            page += &format!(" from {}", markdown::location_link(location, imgui_url));
+        }
+        page += ".\n";
+        if let Some(comment) = &self.comment {
+            page += &format!("\n{}", markdown::paragraph(comment));
+        }
+
+        let fields = self
+            .doc_fields(types, Annotation::LuaLs)
+            .map(|(name, r#type)| vec![markdown::code(name), markdown::code(&r#type)])
+            .collect::<Vec<_>>();
+        if !fields.is_empty() {
+            page += &format!(
+                "\n## Fields\n\n{}",
+                markdown::table(&["Field", "Type"], &fields)
+            );
+        }
+
+        let methods = self
+            .bound_methods()
+            .map(|(key, method)| {
+                method.markdown(
+                    &format!("gui.{}.{}", self.name.lua_type(), key),
+                    types,
+                    imgui_url,
+                )
+            })
+            .collect::<Vec<_>>();
+        if !methods.is_empty() {
+            page += &format!("\n## Methods\n\n{}", methods.join("\n"));
+        }
+
+        page
+    }
+
+    /// The methods with the key they're bound to in Lua, constructors are bound to `new` and the
+    /// ones split off from it to `new_` with the overload suffix, such as `new_float`.
+    pub fn bound_methods(&self) -> impl Iterator<Item = (String, &Overloads)> {
+        self.methods
+            .iter()
+            .filter_map(|method| match method.functions()[0].kind() {
+                // Objects are destroyed by the garbage collector
+                Kind::Destructor => None,
+                Kind::Constructor { .. } => {
+                    let suffix = method
+                        .name()
+                        .imgui()
+                        .strip_prefix(method.functions()[0].name().imgui())
+                        .unwrap_or_default();
+                    let key = if suffix.is_empty() {
+                        "new".to_string()
+                    } else {
+                        format!("new_{}", Name::from(suffix).lua())
+                    };
+
+                    Some((key, method))
+                }
+                Kind::Function => Some((method.name().lua(), method)),
+            })
+    }
+
+    /// The names & types of the fields that can be accessed from Lua.
+    fn doc_fields<'a>(
+        &'a self,
+        types: &'a [Type],
+        annotation: Annotation,
+    ) -> impl Iterator<Item = (&'a str, String)> {
+        self.fields
+            .iter()
+            // Skip the fields cimgui couldn't name, such as the members of unions
+            .filter(|field| name::is_lua_identifier(field.name.imgui()))
+            .map(move |field| {
+                let r#type = if field.r#type.is_string() {
+                    // Only arguments & return values are converted to Lua strings
+                    annotation.cdata().to_string()
+                } else {
+                    function::doc_type(&field.r#type, types, annotation)
+                };
+
+                (field.name.imgui(), r#type)
+            })
+    }
+
+    /// The types this struct contains by value, they must be declared before this struct.
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().filter_map(|field| field.value_type())
+    }
+}
+
+impl Render for Struct {
+    /// Get the table with the methods, bound to the C type with a metatype.
+    fn lua(&self, types: &[Type]) -> String {
+        if self.methods.is_empty() {
+            return String::new();
+        }
+
+        let path = format!("gui.{}", self.name.lua_type());
+
+        format!(
+            indoc!(
+                r#"
+            {path} = {{}}
+            {path}.__index = {path}
+
+            {methods}
+            ffi.metatype("{name}", {path})
+            "#
+            ),
+            path = path,
+            name = self.name.imgui(),
+            methods = self
+                .bound_methods()
+                .map(|(key, method)| format!(
+                    "{}{}",
+                    comment::prefixed(method.comment().as_deref(), "---"),
+                    method.lua_function(&format!("{}.{}", path, key), types)
+                ))
+                .join("\n"),
+        )
+    }
+
+    /// Get the LuaLS class with the fields & methods.
+    fn doc(&self, types: &[Type]) -> String {
+        std::iter::once(comment::prefixed(self.comment.as_deref(), "---"))
+            .chain(std::iter::once(format!(
+                "---@class {}\n",
+                self.name.lua_type()
+            )))
+            .chain(
+                self.doc_fields(types, Annotation::LuaLs)
+                    .map(|(name, r#type)| format!("---@field {} {}\n", name, r#type)),
+            )
+            .chain(self.bound_methods().map(|(key, method)| {
+                format!("---@field {} {}\n", key, method.doc_signature(types))
+            }))
+            .collect()
+    }
+
+    /// Get the Teal record with the fields & methods.
+    fn teal(&self, types: &[Type]) -> String {
+        format!(
+            "{comment}record {name}\n{array}{fields}{methods}end\n",
+            comment = comment::prefixed(self.comment.as_deref(), "--"),
+            name = self.name.lua_type(),
+            // Vectors can also be passed as arrays of numbers
+            array = match self.name.imgui() {
+                "ImVec2" | "ImVec4" => "    {number}\n",
+                _ => "",
+            },
+            fields = self
+                .doc_fields(types, Annotation::Teal)
+                .map(|(name, r#type)| format!("    {}: {}\n", name, r#type))
+                .collect::<String>(),
+            methods = self
+                .bound_methods()
+                .flat_map(|(key, method)| {
+                    method.functions().iter().map(move |func| {
+                        format!(
+                            "{}    {}: {}\n",
+                            comment::prefixed(func.comment(), "    --"),
+                            key,
+                            func.teal_signature(types)
+                        )
+                    })
+                })
+                .collect::<String>()
+        )
+    }
+
+    /// Get the cdef definition of the struct body.
+    fn cdef(&self, _types: &[Type]) -> String {
+        format!(
+            "struct {name} {{\n{fields}\n}};",
+            name = self.name.imgui(),
+            fields = self
+                .fields
+                .iter()
+                .map(|field| format!("    {}", field.cdef()))
+                .join("\n")
+        )
+    }
 }
 
 /// Represents an ImGui structure field.
 #[derive(Debug)]
 pub struct Field {
     name: Name,
+    bitfield: Option<String>,
     template_type: Option<String>,
-    r#type: String,
+    r#type: CType,
 }
 
 impl Field {
     /// Add a new struct field from the parsed data.
-    pub fn from_parsed(name: Name, template_type: Option<String>, r#type: String) -> Self {
+    pub fn from_parsed(
+        name: Name,
+        bitfield: Option<String>,
+        template_type: Option<String>,
+        r#type: CType,
+    ) -> Self {
         Self {
             name,
+            bitfield,
             template_type,
             r#type,
         }
     }
+
+    /// The type when the field is a templated `ImVector<T>`, with the template argument.
+    pub fn template(&self) -> Option<(&str, &str)> {
+        match (self.r#type.name(), &self.template_type) {
+            (Some(name), Some(template_type)) => Some((name, template_type)),
+            _ => None,
+        }
+    }
+
+    /// The C type of the field.
+    pub fn c_type(&self) -> &CType {
+        &self.r#type
+    }
+
+    /// The name of the type if it's contained by value.
+    pub fn value_type(&self) -> Option<&str> {
+        // Pointers & function pointers don't need a complete type
+        if self.r#type.is_indirect() {
+            None
+        } else {
+            self.r#type.name()
+        }
+    }
+
+    /// The field declaration inside a C struct.
+    pub fn cdef(&self) -> String {
+        let declaration = self.r#type.declaration(self.name.imgui());
+
+        match &self.bitfield {
+            Some(bits) => format!("{} : {};", declaration, bits),
+            None => format!("{};", declaration),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        c_type::CType,
+        function::{Arg, Function, Kind},
+        overload::Overloads,
+        render::Render,
+    };
+
+    #[test]
+    fn lua() -> anyhow::Result<()> {
+        let mut r#struct = super::Struct::from_parsed("ImGuiTextFilter".into(), vec![]);
+        r#struct.add_methods(Overloads::group(
+            vec![
+                Function::from_parsed(
+                    "IsActive".into(),
+                    "ImGuiTextFilter_IsActive".into(),
+                    vec![Arg::from_parsed(
+                        "self".to_string(),
+                        None,
+                        CType::parse("ImGuiTextFilter*")?,
+                        1,
+                    )],
+                    None,
+                    Some(CType::parse("bool")?),
+                ),
+                Function::from_parsed(
+                    "ImGuiTextFilter".into(),
+                    "ImGuiTextFilter_ImGuiTextFilter".into(),
+                    vec![Arg::from_parsed(
+                        "default_filter".to_string(),
+                        Some("\"\"".to_string()),
+                        CType::parse("const char*")?,
+                        1,
+                    )],
+                    None,
+                    Some(CType::parse("ImGuiTextFilter*")?),
+                )
+                .with_kind(Kind::Constructor {
+                    destructor: Some("ImGuiTextFilter_destroy".into()),
+                }),
+                Function::from_parsed(
+                    "destroy".into(),
+                    "ImGuiTextFilter_destroy".into(),
+                    vec![Arg::from_parsed(
+                        "self".to_string(),
+                        None,
+                        CType::parse("ImGuiTextFilter*")?,
+                        1,
+                    )],
+                    None,
+                    None,
+                )
+                .with_kind(Kind::Destructor),
+            ],
+            &[],
+        ));
+
+        assert_eq!(
+            r#struct.lua(&[]),
+            indoc::indoc!(
+                r#"
+                gui.TextFilter = {}
+                gui.TextFilter.__index = gui.TextFilter
+
+                function gui.TextFilter.new(default_filter)
+                    if default_filter == nil then
+                        default_filter = ""
+                    end
+                    default_filter = args_check(default_filter, "string", 1, "default_filter")
+                    local ret = C.ImGuiTextFilter_ImGuiTextFilter(default_filter)
+                    return ffi.gc(ret, C.ImGuiTextFilter_destroy)
+                end
+
+                function gui.TextFilter.is_active(self)
+                    self = args_check(self, "cdata", 1, "self")
+                    return C.ImGuiTextFilter_IsActive(self)
+                end
+
+                ffi.metatype("ImGuiTextFilter", gui.TextFilter)
+                "#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lua_unreachable_constructor() -> anyhow::Result<()> {
+        let constructor = |suffix: &str, val_type: &str| -> anyhow::Result<Function> {
+            Ok(Function::from_parsed(
+                "ImGuiStoragePair".into(),
+                format!("ImGuiStoragePair_ImGuiStoragePair{}", suffix).into(),
+                vec![
+                    Arg::from_parsed("_key".to_string(), None, CType::parse("unsigned int")?, 1),
+                    Arg::from_parsed("_val".to_string(), None, CType::parse(val_type)?, 2),
+                ],
+                None,
+                Some(CType::parse("ImGuiStoragePair*")?),
+            )
+            .with_kind(Kind::Constructor { destructor: None }))
+        };
+
+        let mut r#struct = super::Struct::from_parsed("ImGuiStoragePair".into(), vec![]);
+        r#struct.add_methods(Overloads::group(
+            vec![constructor("Int", "int")?, constructor("Float", "float")?],
+            &[],
+        ));
+
+        // Both take numbers, the one that can't be reached gets a key of its own
+        let lua = r#struct.lua(&[]);
+        assert_eq!(lua.matches("function gui.StoragePair.new(").count(), 1);
+        assert_eq!(lua.matches("function gui.StoragePair.new_int(").count(), 1);
+        assert!(lua.contains("C.ImGuiStoragePair_ImGuiStoragePairInt(_key, _val)"));
+        let teal = r#struct.teal(&[]);
+        assert_eq!(teal.matches("    new:").count(), 1);
+        assert_eq!(teal.matches("    new_int:").count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn field_cdef() -> anyhow::Result<()> {
+        // cimgui splits an inline union between the type & the name of the field
+        let field = super::Field::from_parsed(
+            "}".into(),
+            None,
+            None,
+            CType::parse("union { int val_i; float val_f; void* val_p;")?,
+        );
+
+        assert_eq!(
+            field.cdef(),
+            "union { int val_i; float val_f; void* val_p; };"
+        );
+
+        Ok(())
+    }
 }
@@ -1,21 +1,26 @@
-use crate::{function::Function, r#enum::Enum, r#struct::Struct};
+use crate::{
+    overload::Overloads, r#enum::Enum, r#struct::Struct, render::Render, typedef::Typedef,
+};
 use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use std::collections::HashSet;
 
-/// Represents any ImGui or C type.
+/// Represents any ImGui type.
 #[derive(Debug)]
 pub enum Type {
     Enum(Enum),
     Struct(Struct),
-    C(String),
+    Typedef(Typedef),
 }
 
 impl Type {
-    /// Get a list of default C types.
-    pub fn default_list() -> Vec<Self> {
-        vec!["char*", "int", "float"]
-            .into_iter()
-            .map(|t| Self::C(t.to_string()))
-            .collect()
+    /// The ImGui name of the type.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Enum(r#enum) => r#enum.name(),
+            Self::Struct(r#struct) => r#struct.name().imgui(),
+            Self::Typedef(typedef) => typedef.name().imgui(),
+        }
     }
 
     /// Check if this type is the same as the string.
@@ -23,7 +28,7 @@ impl Type {
         match self {
             Self::Enum(r#enum) => r#enum.is_same(r#type),
             Self::Struct(r#struct) => r#struct.is_same(r#type),
-            Self::C(c) => c == r#type,
+            Self::Typedef(typedef) => typedef.is_same(r#type),
         }
     }
 
@@ -36,12 +41,24 @@ impl Type {
         }
     }
 
-    /// Add a method to the type (only applies to structs).
-    pub fn add_method(&mut self, method: Function) -> Result<()> {
+    /// Add the documentation from the comments in the ImGui header to the type.
+    pub fn add_comment(&mut self, comment: String) {
+        match self {
+            Self::Enum(r#enum) => r#enum.add_comment(comment),
+            Self::Struct(r#struct) => r#struct.add_comment(comment),
+            _ => (),
+        }
+    }
+
+    /// Add methods to the type (only applies to structs).
+    pub fn add_methods(&mut self, methods: Vec<Overloads>) -> Result<()> {
         match self {
-            Self::Struct(r#struct) => Ok(r#struct.add_method(method)),
+            Self::Struct(r#struct) => {
+                r#struct.add_methods(methods);
+                Ok(())
+            }
             Self::Enum(_) => Err(anyhow!("Cannot add method to enum")),
-            Self::C(_) => Err(anyhow!("Cannot add method to C type")),
+            Self::Typedef(_) => Err(anyhow!("Cannot add method to typedef")),
         }
     }
 
@@ -52,12 +69,45 @@ impl Type {
             Self::Struct(_) => Ok("table".to_string()),
             // Enums are always a number.
             Self::Enum(_) => Ok("number".to_string()),
-            Self::C(c_type) => match c_type.as_str() {
-                "int" | "float" => Ok("number".to_string()),
-                "bool" => Ok("boolean".to_string()),
-                "const char*" | "char*" => Ok("string".to_string()),
-                other => Err(anyhow!("Unrecognized C type \"{}\"", other)),
-            },
+            // Typedefs that can't be resolved are function pointers or opaque.
+            Self::Typedef(typedef) => Err(anyhow!(
+                "Unresolvable typedef \"{}\"",
+                typedef.name().imgui()
+            )),
+        }
+    }
+}
+
+impl Render for Type {
+    fn lua(&self, types: &[Type]) -> String {
+        match self {
+            Self::Enum(r#enum) => r#enum.lua(types),
+            Self::Struct(r#struct) => r#struct.lua(types),
+            Self::Typedef(typedef) => typedef.lua(types),
+        }
+    }
+
+    fn doc(&self, types: &[Type]) -> String {
+        match self {
+            Self::Enum(r#enum) => r#enum.doc(types),
+            Self::Struct(r#struct) => r#struct.doc(types),
+            Self::Typedef(typedef) => typedef.doc(types),
+        }
+    }
+
+    fn teal(&self, types: &[Type]) -> String {
+        match self {
+            Self::Enum(r#enum) => r#enum.teal(types),
+            Self::Struct(r#struct) => r#struct.teal(types),
+            Self::Typedef(typedef) => typedef.teal(types),
+        }
+    }
+
+    fn cdef(&self, types: &[Type]) -> String {
+        match self {
+            Self::Enum(r#enum) => r#enum.cdef(types),
+            Self::Struct(r#struct) => r#struct.cdef(types),
+            Self::Typedef(typedef) => typedef.cdef(types),
         }
     }
 }
@@ -68,11 +118,70 @@ pub trait TypeList {
 
     /// Find a type by it's ImGui name.
     fn find(&self, imgui_type: &str) -> Result<&Self::Output>;
+
+    /// All typedefs, enums & structs in the order they must be declared, typedefs & structs are
+    /// sorted so that every type they depend on comes first.
+    fn declaration_order(&self) -> Vec<&Self::Output>;
 }
 
-impl TypeList for Vec<Type> {
+impl TypeList for [Type] {
     type Output = Type;
 
+    fn declaration_order(&self) -> Vec<&Type> {
+        // Depth-first topological sort on the dependencies of the same kind of type
+        fn visit<'a>(
+            r#type: &'a Type,
+            candidates: &[&'a Type],
+            visited: &mut HashSet<&'a str>,
+            ordered: &mut Vec<&'a Type>,
+        ) {
+            if !visited.insert(r#type.name()) {
+                return;
+            }
+
+            let dependencies: Vec<&str> = match r#type {
+                Type::Struct(r#struct) => r#struct.dependencies().collect(),
+                Type::Typedef(typedef) => typedef.dependency().into_iter().collect(),
+                _ => vec![],
+            };
+            for dependency in dependencies {
+                if let Some(dependency) =
+                    candidates.iter().find(|r#type| r#type.is_same(dependency))
+                {
+                    visit(dependency, candidates, visited, ordered);
+                }
+            }
+
+            ordered.push(r#type);
+        }
+
+        let sorted = |filter: fn(&&Type) -> bool| {
+            self.iter()
+                .filter(filter)
+                .sorted_by_key(|r#type| r#type.name())
+                .collect::<Vec<_>>()
+        };
+
+        let mut ordered = vec![];
+        let mut visited = HashSet::new();
+
+        // Typedefs are needed by the struct fields
+        let typedefs = sorted(|r#type| matches!(r#type, Type::Typedef(_)));
+        for typedef in typedefs.iter() {
+            visit(typedef, &typedefs, &mut visited, &mut ordered);
+        }
+
+        // Enums don't depend on anything
+        ordered.extend(sorted(|r#type| matches!(r#type, Type::Enum(_))));
+
+        let structs = sorted(|r#type| matches!(r#type, Type::Struct(_)));
+        for r#struct in structs.iter() {
+            visit(r#struct, &structs, &mut visited, &mut ordered);
+        }
+
+        ordered
+    }
+
     fn find(&self, imgui_type: &str) -> Result<&Type> {
         self.iter()
             .find(|r#type| r#type.is_same(imgui_type))
@@ -89,13 +198,80 @@ impl TypeList for Vec<Type> {
 mod tests {
     #[test]
     fn lua_type() -> anyhow::Result<()> {
+        use crate::{c_type::CType, r#enum::Enum};
+
+        let types = [super::Type::Enum(Enum::from_parsed(
+            "ImGuiCol_".to_string(),
+            vec![],
+        ))];
+
+        assert_eq!(CType::parse("int")?.lua_type(&types)?, "number");
+        assert_eq!(CType::parse("unsigned short")?.lua_type(&types)?, "number");
+        assert_eq!(CType::parse("bool")?.lua_type(&types)?, "boolean");
+        assert_eq!(CType::parse("const char*")?.lua_type(&types)?, "string");
+        assert_eq!(CType::parse("float*")?.lua_type(&types)?, "cdata");
+        assert_eq!(CType::parse("ImGuiCol_")?.lua_type(&types)?, "number");
+
+        Ok(())
+    }
+
+    #[test]
+    fn declaration_order() -> anyhow::Result<()> {
+        use super::{Type, TypeList};
+        use crate::{
+            c_type::CType,
+            r#struct::{Field, Struct},
+        };
+
+        let types = [
+            Type::Struct(Struct::from_parsed(
+                "ImDrawList".into(),
+                vec![
+                    Field::from_parsed(
+                        "CmdBuffer".into(),
+                        None,
+                        None,
+                        CType::parse("ImVector_ImDrawCmd")?,
+                    ),
+                    Field::from_parsed(
+                        "_Data".into(),
+                        None,
+                        None,
+                        CType::parse("ImDrawListSharedData*")?,
+                    ),
+                ],
+            )),
+            Type::Struct(Struct::from_template(
+                "ImVector_ImDrawCmd".into(),
+                "ImDrawCmd",
+            )?),
+            Type::Struct(Struct::from_parsed(
+                "ImDrawCmd".into(),
+                vec![Field::from_parsed(
+                    "ClipRect".into(),
+                    None,
+                    None,
+                    CType::parse("ImVec4")?,
+                )],
+            )),
+            Type::Struct(Struct::from_parsed(
+                "ImVec4".into(),
+                vec![Field::from_parsed(
+                    "x".into(),
+                    None,
+                    None,
+                    CType::parse("float")?,
+                )],
+            )),
+        ];
+
         assert_eq!(
-            super::Type::C("int".to_string()).lua_primitive_type()?,
-            "number"
-        );
-        assert_eq!(
-            super::Type::C("const char*".to_string()).lua_primitive_type()?,
-            "string"
+            types
+                .declaration_order()
+                .into_iter()
+                .map(|r#type| r#type.name())
+                .collect::<Vec<_>>(),
+            vec!["ImVec4", "ImDrawCmd", "ImVector_ImDrawCmd", "ImDrawList"]
         );
 
         Ok(())
@@ -0,0 +1,150 @@
+use crate::{
+    c_type::CType,
+    name::Name,
+    r#type::{Type, TypeList},
+    render::Render,
+};
+
+/// Represents a C typedef, an alias for another type.
+#[derive(Debug)]
+pub struct Typedef {
+    /// The name of the alias.
+    name: Name,
+    /// The C type it's an alias for.
+    r#type: CType,
+}
+
+impl Typedef {
+    /// Add a new typedef from the parsed data.
+    pub fn from_parsed(name: Name, r#type: CType) -> Self {
+        Self { name, r#type }
+    }
+
+    /// Check if this type is the same as the string.
+    pub fn is_same(&self, r#type: &str) -> bool {
+        self.name.imgui() == r#type
+    }
+
+    /// The name of the typedef.
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    /// The C type it's an alias for.
+    pub fn c_type(&self) -> &CType {
+        &self.r#type
+    }
+
+    /// The name of the type this is an alias for.
+    pub fn dependency(&self) -> Option<&str> {
+        self.r#type.name()
+    }
+
+    /// The type this is an alias for, `None` for function pointers & opaque structs.
+    pub fn target<'a>(&self, types: &'a [Type]) -> Option<&'a Type> {
+        // cimgui flag & enum typedefs are plain integers, but the enum with the same name is
+        // suffixed with an underscore
+        if let Ok(r#enum) = types.find(&format!("{}_", self.name.imgui())) {
+            if let Type::Enum(_) = r#enum {
+                return Some(r#enum);
+            }
+        }
+
+        self.dependency()
+            .filter(|dependency| *dependency != self.name.imgui())
+            .and_then(|dependency| types.find(dependency).ok())
+    }
+}
+
+impl Render for Typedef {
+    fn lua(&self, _types: &[Type]) -> String {
+        String::new()
+    }
+
+    fn doc(&self, _types: &[Type]) -> String {
+        String::new()
+    }
+
+    fn teal(&self, _types: &[Type]) -> String {
+        String::new()
+    }
+
+    /// Get the cdef definition of the typedef.
+    fn cdef(&self, _types: &[Type]) -> String {
+        if self.dependency() == Some(self.name.imgui()) {
+            // Opaque structs
+            format!("typedef struct {name} {name};", name = self.name.imgui())
+        } else {
+            format!("typedef {};", self.r#type.declaration(self.name.imgui()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{c_type::CType, render::Render};
+
+    #[test]
+    fn cdef() -> anyhow::Result<()> {
+        let typedef = super::Typedef::from_parsed("ImWchar".into(), CType::parse("ImWchar16")?);
+        assert_eq!(typedef.cdef(&[]), "typedef ImWchar16 ImWchar;");
+
+        let typedef = super::Typedef::from_parsed(
+            "ImGuiInputTextCallback".into(),
+            CType::parse("int(*)(ImGuiInputTextCallbackData *data);")?,
+        );
+        assert_eq!(
+            typedef.cdef(&[]),
+            "typedef int(*ImGuiInputTextCallback)(ImGuiInputTextCallbackData* data);"
+        );
+
+        let typedef = super::Typedef::from_parsed(
+            "ImGuiContext".into(),
+            CType::parse("struct ImGuiContext")?,
+        );
+        assert_eq!(
+            typedef.cdef(&[]),
+            "typedef struct ImGuiContext ImGuiContext;"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolve() -> anyhow::Result<()> {
+        use crate::{r#enum::Enum, r#type::Type};
+
+        let mut types = vec![Type::Enum(Enum::from_parsed(
+            "ImGuiWindowFlags_".to_string(),
+            vec![],
+        ))];
+        for (name, r#type) in [
+            ("ImWchar16", "unsigned short"),
+            ("ImWchar", "ImWchar16"),
+            ("ImGuiWindowFlags", "int"),
+            ("ImGuiContext", "struct ImGuiContext"),
+        ]
+        .iter()
+        {
+            types.push(Type::Typedef(super::Typedef::from_parsed(
+                (*name).into(),
+                CType::parse(r#type)?,
+            )));
+        }
+
+        assert_eq!(
+            CType::parse("const ImWchar*")?.resolve(&types),
+            CType::parse("const unsigned short*")?
+        );
+        assert_eq!(
+            CType::parse("ImGuiWindowFlags")?.resolve(&types),
+            CType::parse("ImGuiWindowFlags_")?
+        );
+        assert_eq!(
+            CType::parse("ImGuiContext*")?.resolve(&types),
+            CType::parse("ImGuiContext*")?
+        );
+
+        Ok(())
+    }
+}